@@ -0,0 +1,712 @@
+/// A public item of a crate as seen by API diffs
+#[derive(Debug, Clone)]
+pub struct ApiItem {
+    pub path: crate::doc::ItemPath,
+    /// Other public paths of the item (e.g., re-exports)
+    pub aliases: Vec<crate::doc::ItemPath>,
+    pub kind: crate::doc::ItemKind,
+    pub signature: String,
+    /// Whether implementors must define this trait item (i.e., it has no default)
+    pub is_required: bool,
+    /// Whether users can construct or exhaustively match this struct, union, enum or variant
+    pub is_exhaustive: bool,
+    /// Whether this is an impl of a trait (as opposed to an inherent impl)
+    pub is_trait_impl: bool,
+    /// Path of the impl defining the item (members of the impls of a type share their paths)
+    pub impl_path: Option<crate::doc::ItemPath>,
+    /// Number of earlier items with the same path, kind and impl path
+    /// (e.g., the second inherent impl of a type)
+    pub occurrence: usize,
+}
+
+impl ApiItem {
+    /// Collects the public items of `doc` (`use` items are covered by the items they re-export)
+    pub fn collect(doc: &crate::doc::CrateDoc) -> crate::Result<Vec<Self>> {
+        let mut impl_paths = std::collections::HashMap::new();
+        for (path, item) in &doc.show_items {
+            if item.kind == crate::doc::ItemKind::Impl {
+                let member_ids = item.inner(doc.json()).to_member("items")?.required()?;
+                for member_id in member_ids.to_array()? {
+                    let member_id: crate::doc::ItemId = member_id.try_into()?;
+                    impl_paths.insert(member_id, path);
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        let mut occurrences = std::collections::HashMap::new();
+        for (path, item) in &doc.show_items {
+            if item.kind == crate::doc::ItemKind::Use {
+                continue;
+            }
+            let impl_path = impl_paths.get(&item.id).map(|&path| path.clone());
+            let occurrence = occurrences
+                .entry((path, item.kind, impl_path.clone()))
+                .or_insert(0);
+            let signature = if item.kind == crate::doc::ItemKind::Module {
+                // Module signatures include child counts, which would duplicate child changes
+                format!("mod {};", path.name())
+            } else {
+                crate::format_item::format_signature_to_string(doc, item, false)?
+            };
+            let inner = item.inner(doc.json());
+            items.push(Self {
+                path: path.clone(),
+                aliases: doc.aliases(item).to_vec(),
+                kind: item.kind,
+                signature,
+                is_required: is_required_trait_item(item.kind, inner)?,
+                is_exhaustive: is_exhaustive(doc, item)?,
+                is_trait_impl: is_trait_impl(item.kind, inner)?,
+                impl_path,
+                occurrence: *occurrence,
+            });
+            *occurrence += 1;
+        }
+        Ok(items)
+    }
+
+    fn paths(&self) -> impl Iterator<Item = &crate::doc::ItemPath> {
+        std::iter::once(&self.path).chain(&self.aliases)
+    }
+
+    /// Returns the key of the item under `path` (its canonical path or one of its aliases)
+    fn key<'a>(&'a self, path: &'a crate::doc::ItemPath) -> PathKey<'a> {
+        (path, self.kind, self.impl_path.as_ref(), self.occurrence)
+    }
+
+    /// Returns the part of the signature before the member list (the whole signature for functions)
+    fn declaration(&self) -> &str {
+        match self.kind {
+            crate::doc::ItemKind::Struct
+            | crate::doc::ItemKind::Union
+            | crate::doc::ItemKind::Enum
+            | crate::doc::ItemKind::Trait
+            | crate::doc::ItemKind::Impl => self
+                .signature
+                .split_once('{')
+                .map_or(self.signature.as_str(), |(declaration, _)| declaration)
+                .trim_end(),
+            _ => &self.signature,
+        }
+    }
+}
+
+fn is_required_trait_item(
+    kind: crate::doc::ItemKind,
+    inner: nojson::RawJsonValue,
+) -> crate::Result<bool> {
+    let default_member = match kind {
+        crate::doc::ItemKind::Function => {
+            let has_body: bool = inner.to_member("has_body")?.required()?.try_into()?;
+            return Ok(!has_body);
+        }
+        crate::doc::ItemKind::AssocType => "type",
+        crate::doc::ItemKind::AssocConst => "value",
+        _ => return Ok(false),
+    };
+    Ok(inner
+        .to_member(default_member)?
+        .get()
+        .is_none_or(|v| v.kind().is_null()))
+}
+
+fn is_trait_impl(kind: crate::doc::ItemKind, inner: nojson::RawJsonValue) -> crate::Result<bool> {
+    if kind != crate::doc::ItemKind::Impl {
+        return Ok(false);
+    }
+    Ok(!inner.to_member("trait")?.required()?.kind().is_null())
+}
+
+fn is_exhaustive(doc: &crate::doc::CrateDoc, item: &crate::doc::Item) -> crate::Result<bool> {
+    let inner = item.inner(doc.json());
+    let has_stripped_fields = match item.kind {
+        crate::doc::ItemKind::Struct | crate::doc::ItemKind::Variant => {
+            let kind = inner.to_member("kind")?.required()?;
+            if !kind.kind().is_object() {
+                // Unit structs and plain variants
+                false
+            } else if let Some(fields) = kind
+                .to_member("plain")?
+                .get()
+                .or(kind.to_member("struct")?.get())
+            {
+                fields
+                    .to_member("has_stripped_fields")?
+                    .required()?
+                    .try_into()?
+            } else if let Some(fields) = kind.to_member("tuple")?.get() {
+                // Private tuple fields are represented as `null`
+                fields.to_array()?.any(|field| field.kind().is_null())
+            } else {
+                false
+            }
+        }
+        crate::doc::ItemKind::Union => inner
+            .to_member("has_stripped_fields")?
+            .required()?
+            .try_into()?,
+        crate::doc::ItemKind::Enum => false,
+        _ => return Ok(false),
+    };
+    Ok(!has_stripped_fields && !has_non_exhaustive_attr(doc, item)?)
+}
+
+fn has_non_exhaustive_attr(
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+) -> crate::Result<bool> {
+    let Some(value) = doc.items.get_by_id(doc.json(), item.id) else {
+        return Ok(false);
+    };
+    let Some(attrs) = value.to_member("attrs")?.get() else {
+        return Ok(false);
+    };
+    for attr in attrs.to_array()? {
+        // Unit attributes are plain strings (see `crate::compat::upgrade` for older formats)
+        if attr.kind().is_string() && attr.to_unquoted_string_str()? == "non_exhaustive" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Semantic versioning impact of a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Patch => "patch",
+            Severity::Minor => "minor",
+            Severity::Major => "major",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Removed,
+    Changed,
+    Added,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+            ChangeKind::Added => "added",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiChange {
+    pub change: ChangeKind,
+    pub path: crate::doc::ItemPath,
+    pub kind: crate::doc::ItemKind,
+    pub old_signature: Option<String>,
+    pub new_signature: Option<String>,
+    pub severity: Severity,
+    pub reason: &'static str,
+}
+
+impl nojson::DisplayJson for ApiChange {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("change", self.change.as_str())?;
+            f.member("path", self.path.to_string())?;
+            f.member("kind", self.kind.as_keyword_str())?;
+            f.member("severity", self.severity.as_str())?;
+            f.member("reason", self.reason)?;
+            f.member("old_signature", &self.old_signature)?;
+            f.member("new_signature", &self.new_signature)
+        })
+    }
+}
+
+/// Compares the public APIs of two versions of a crate
+///
+/// Items are matched by canonical path and kind (and, for impls and their members, by the impl
+/// they belong to and their order). An item whose canonical path moved but that is still
+/// reachable through a re-export is matched by its alias paths. Public paths that a matched
+/// item loses or gains (e.g., a removed `pub use`) are reported as removals or additions.
+pub fn diff(
+    old_doc: &crate::doc::CrateDoc,
+    new_doc: &crate::doc::CrateDoc,
+) -> crate::Result<Vec<ApiChange>> {
+    let old_items =
+        ApiItem::collect(old_doc).map_err(|e| e.set_json_text(old_doc.json().text()))?;
+    let new_items =
+        ApiItem::collect(new_doc).map_err(|e| e.set_json_text(new_doc.json().text()))?;
+    let old_index = PathIndex::new(&old_items);
+    let new_index = PathIndex::new(&new_items);
+
+    let mut changes = Vec::new();
+    for old_item in &old_items {
+        let Some(i) = new_index.find(old_item) else {
+            changes.push(ApiChange {
+                change: ChangeKind::Removed,
+                path: old_item.path.clone(),
+                kind: old_item.kind,
+                old_signature: Some(old_item.signature.clone()),
+                new_signature: None,
+                severity: Severity::Major,
+                reason: if old_item.is_trait_impl {
+                    "trait impl removed"
+                } else {
+                    "public item removed"
+                },
+            });
+            continue;
+        };
+        let new_item = &new_items[i];
+        for path in old_item
+            .paths()
+            .filter(|&p| !new_item.paths().any(|x| x == p))
+        {
+            changes.push(ApiChange {
+                change: ChangeKind::Removed,
+                path: path.clone(),
+                kind: old_item.kind,
+                old_signature: Some(old_item.signature.clone()),
+                new_signature: None,
+                severity: Severity::Major,
+                reason: "re-export removed",
+            });
+        }
+        for path in new_item
+            .paths()
+            .filter(|&p| !old_item.paths().any(|x| x == p))
+        {
+            changes.push(ApiChange {
+                change: ChangeKind::Added,
+                path: path.clone(),
+                kind: new_item.kind,
+                old_signature: None,
+                new_signature: Some(new_item.signature.clone()),
+                severity: Severity::Minor,
+                reason: "re-export added",
+            });
+        }
+        if let Some((severity, reason)) = classify_change(old_item, new_item) {
+            changes.push(ApiChange {
+                change: ChangeKind::Changed,
+                path: new_item.path.clone(),
+                kind: old_item.kind,
+                old_signature: Some(old_item.signature.clone()),
+                new_signature: Some(new_item.signature.clone()),
+                severity,
+                reason,
+            });
+        }
+    }
+    for new_item in &new_items {
+        if old_index.find(new_item).is_none() {
+            let parent = new_item
+                .path
+                .parent()
+                .and_then(|path| new_index.find_parent(&new_items, &path));
+            let (severity, reason) = classify_addition(new_item, parent);
+            changes.push(ApiChange {
+                change: ChangeKind::Added,
+                path: new_item.path.clone(),
+                kind: new_item.kind,
+                old_signature: None,
+                new_signature: Some(new_item.signature.clone()),
+                severity,
+                reason,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| {
+        (a.change, a.path.to_string(), a.kind.as_keyword_str()).cmp(&(
+            b.change,
+            b.path.to_string(),
+            b.kind.as_keyword_str(),
+        ))
+    });
+    Ok(changes)
+}
+
+/// Returns the overall semver impact of `changes` (`None` if there are no changes)
+pub fn required_bump(changes: &[ApiChange]) -> Option<Severity> {
+    changes.iter().map(|x| x.severity).max()
+}
+
+fn classify_change(old: &ApiItem, new: &ApiItem) -> Option<(Severity, &'static str)> {
+    use crate::doc::ItemKind;
+
+    if old.is_exhaustive && !new.is_exhaustive {
+        return Some((
+            Severity::Major,
+            "became non-exhaustive or gained private fields",
+        ));
+    }
+    if !old.is_required && new.is_required {
+        return Some((Severity::Major, "trait item default removed"));
+    }
+    if old.signature == new.signature {
+        if old.is_exhaustive != new.is_exhaustive || old.is_required != new.is_required {
+            return Some((Severity::Minor, "restriction relaxed"));
+        }
+        return None;
+    }
+
+    let severity_and_reason = match old.kind {
+        ItemKind::Macro => (Severity::Minor, "macro definition changed"),
+        _ if old.declaration() == new.declaration() => {
+            // Member changes are classified as separate items
+            (Severity::Patch, "members changed")
+        }
+        ItemKind::Function => (Severity::Major, "function signature changed"),
+        ItemKind::Impl => (Severity::Major, "impl bounds changed"),
+        _ => (Severity::Major, "declaration changed"),
+    };
+    Some(severity_and_reason)
+}
+
+fn classify_addition(item: &ApiItem, parent: Option<&ApiItem>) -> (Severity, &'static str) {
+    use crate::doc::ItemKind;
+
+    match (item.kind, parent.map(|p| (p.kind, p.is_exhaustive))) {
+        (_, Some((ItemKind::Trait, _))) if item.is_required => {
+            (Severity::Major, "new required trait item")
+        }
+        (ItemKind::StructField, Some((ItemKind::Variant, _))) => {
+            (Severity::Major, "new field on an enum variant")
+        }
+        (ItemKind::StructField, Some((_, true))) => (
+            Severity::Major,
+            "new field on a struct without private fields",
+        ),
+        (ItemKind::Variant, Some((ItemKind::Enum, true))) => {
+            (Severity::Major, "new variant on an exhaustive enum")
+        }
+        _ => (Severity::Minor, "public item added"),
+    }
+}
+
+/// Key identifying an item within a crate: path, kind, impl path and occurrence
+type PathKey<'a> = (
+    &'a crate::doc::ItemPath,
+    crate::doc::ItemKind,
+    Option<&'a crate::doc::ItemPath>,
+    usize,
+);
+
+/// Maps every public path of items to their indices
+struct PathIndex<'a>(std::collections::HashMap<PathKey<'a>, usize>);
+
+impl<'a> PathIndex<'a> {
+    fn new(items: &'a [ApiItem]) -> Self {
+        let mut index = std::collections::HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            index.insert(item.key(&item.path), i);
+        }
+        // Canonical paths take precedence over aliases
+        for (i, item) in items.iter().enumerate() {
+            for path in &item.aliases {
+                index.entry(item.key(path)).or_insert(i);
+            }
+        }
+        Self(index)
+    }
+
+    /// Finds the trait, struct, union, enum or variant that `path` refers to
+    fn find_parent<'b>(
+        &self,
+        items: &'b [ApiItem],
+        path: &crate::doc::ItemPath,
+    ) -> Option<&'b ApiItem> {
+        use crate::doc::ItemKind;
+
+        [
+            ItemKind::Trait,
+            ItemKind::Struct,
+            ItemKind::Union,
+            ItemKind::Enum,
+            ItemKind::Variant,
+        ]
+        .into_iter()
+        .find_map(|kind| self.0.get(&(path, kind, None, 0)))
+        .map(|&i| &items[i])
+    }
+
+    /// Finds the item matching `item` by canonical path first, then by aliases
+    fn find(&self, item: &ApiItem) -> Option<usize> {
+        item.paths()
+            .find_map(|path| self.0.get(&item.key(path)).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_public_items() -> crate::Result<()> {
+        // `S` moves from `t::inner` to `t` but stays reachable through `pub use`
+        let old_doc = parse_doc(
+            r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2, 3]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "a", "visibility": "public", "inner": {"function": FN_U32}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": "b", "visibility": "public", "inner": {"function": FN_U32}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": "inner", "visibility": "public", "inner": {"module": {"items": [4]}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": "S", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null}
+        }}"#,
+        )?;
+        let new_doc = parse_doc(
+            r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 4, 3, 5]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "a", "visibility": "public", "inner": {"function": FN_U64}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": "inner", "visibility": "public", "inner": {"module": {"items": [6]}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": "S", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "5": {"id": 5, "name": "c", "visibility": "public", "inner": {"function": FN_U32}, "docs": null, "deprecation": null},
+            "6": {"id": 6, "name": null, "visibility": "public", "inner": {"use": {"source": "super::S", "name": "S", "id": 4, "is_glob": false}}, "docs": null, "deprecation": null}
+        }}"#,
+        )?;
+
+        let changes: Vec<_> = diff(&old_doc, &new_doc)?
+            .into_iter()
+            .map(|x| {
+                format!(
+                    "{} ({}) [{}] {}: {:?} -> {:?}",
+                    x.change.as_str(),
+                    x.severity.as_str(),
+                    x.kind.as_keyword_str(),
+                    x.path,
+                    x.old_signature,
+                    x.new_signature
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                r#"removed (major) [fn] t::b: Some("fn b(x: u32)") -> None"#,
+                r#"changed (major) [fn] t::a: Some("fn a(x: u32)") -> Some("fn a(x: u64)")"#,
+                r#"added (minor) [struct] t::S: None -> Some("struct S;")"#,
+                r#"added (minor) [fn] t::c: None -> Some("fn c(x: u32)")"#,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_reexports() -> crate::Result<()> {
+        let doc_with_reexport = |use_ids: &str| {
+            format!(
+                r#"{{"format_version": 57, "root": 0, "index": {{
+            "0": {{"id": 0, "name": "t", "visibility": "public", "inner": {{"module": {{"items": [1{use_ids}]}}}}, "docs": null, "deprecation": null}},
+            "1": {{"id": 1, "name": "inner", "visibility": "public", "inner": {{"module": {{"items": [2]}}}}, "docs": null, "deprecation": null}},
+            "2": {{"id": 2, "name": "A", "visibility": "public", "inner": {{"struct": {{"kind": "unit", "impls": []}}}}, "docs": null, "deprecation": null}},
+            "3": {{"id": 3, "name": null, "visibility": "public", "inner": {{"use": {{"source": "inner::A", "name": "A", "id": 2, "is_glob": false}}}}, "docs": null, "deprecation": null}}
+        }}}}"#
+            )
+        };
+        let old_doc = parse_doc(&doc_with_reexport(", 3"))?;
+        let new_doc = parse_doc(&doc_with_reexport(""))?;
+
+        let format_changes = |changes: Vec<ApiChange>| -> Vec<String> {
+            changes
+                .into_iter()
+                .map(|x| {
+                    format!(
+                        "{} ({}) [{}] {}",
+                        x.change.as_str(),
+                        x.severity.as_str(),
+                        x.kind.as_keyword_str(),
+                        x.path
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(
+            format_changes(diff(&old_doc, &new_doc)?),
+            ["removed (major) [struct] t::A"]
+        );
+        assert_eq!(
+            format_changes(diff(&new_doc, &old_doc)?),
+            ["added (minor) [struct] t::A"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_multiple_impls() -> crate::Result<()> {
+        let old_doc = parse_doc(&impls_doc(&[10, 11, 12, 13]))?;
+        assert!(diff(&old_doc, &old_doc)?.is_empty());
+
+        let new_doc = parse_doc(&impls_doc(&[11, 13]))?;
+        let changes: Vec<_> = diff(&old_doc, &new_doc)?
+            .into_iter()
+            .map(|x| {
+                format!(
+                    "{} [{}] {} ({})",
+                    x.change.as_str(),
+                    x.kind.as_keyword_str(),
+                    x.path,
+                    x.reason
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "removed [fn] t::S::f (public item removed)",
+                "removed [impl] t::S::impl (public item removed)",
+                "removed [impl] t::S::impl From<u16> (trait impl removed)"
+            ]
+        );
+        Ok(())
+    }
+
+    /// Returns a doc where `S` has two inherent impls (both defining `f`) and two `From` impls
+    fn impls_doc(impl_ids: &[u32]) -> String {
+        let impl_item = |id: u32, trait_info: &str, member_id: u32| {
+            format!(
+                r#""{id}": {{"id": {id}, "name": null, "visibility": "default", "docs": null, "deprecation": null, "inner": {{"impl": {{"is_unsafe": false, "generics": {{"params": [], "where_predicates": []}}, "provided_trait_methods": [], "trait": {trait_info}, "for": {{"resolved_path": {{"path": "S", "id": 1, "args": null}}}}, "items": [{member_id}], "is_negative": false, "is_synthetic": false, "blanket_impl": null}}}}}},
+                "{member_id}": {{"id": {member_id}, "name": "{}", "visibility": "{}", "inner": {{"function": FN_U32}}, "docs": null, "deprecation": null}}"#,
+                if trait_info == "null" { "f" } else { "from" },
+                if trait_info == "null" {
+                    "public"
+                } else {
+                    "default"
+                },
+            )
+        };
+        let from_trait = |ty: &str| {
+            format!(
+                r#"{{"path": "From", "id": 90, "args": {{"angle_bracketed": {{"args": [{{"type": {{"primitive": "{ty}"}}}}], "constraints": []}}}}}}"#
+            )
+        };
+        let impls = [
+            impl_item(10, "null", 20),
+            impl_item(11, "null", 21),
+            impl_item(12, &from_trait("u16"), 22),
+            impl_item(13, &from_trait("u8"), 23),
+        ];
+        let impl_ids_json: Vec<_> = impl_ids.iter().map(|id| id.to_string()).collect();
+        let impl_items: Vec<_> = impl_ids
+            .iter()
+            .map(|id| impls[*id as usize - 10].as_str())
+            .collect();
+        format!(
+            r#"{{"format_version": 57, "root": 0, "index": {{
+            "0": {{"id": 0, "name": "t", "visibility": "public", "inner": {{"module": {{"items": [1]}}}}, "docs": null, "deprecation": null}},
+            "1": {{"id": 1, "name": "S", "visibility": "public", "inner": {{"struct": {{"kind": "unit", "impls": [{}]}}}}, "docs": null, "deprecation": null}},
+            {}
+        }}}}"#,
+            impl_ids_json.join(", "),
+            impl_items.join(",\n")
+        )
+    }
+
+    #[test]
+    fn classify_semver_impact() {
+        use crate::doc::ItemKind;
+
+        let exhaustive_struct =
+            api_item("t::S", ItemKind::Struct, "struct S { a: u32 }", false, true);
+        let field = api_item("t::S::b", ItemKind::StructField, "b: u32", false, false);
+        assert_eq!(
+            classify_addition(&field, Some(&exhaustive_struct)).0,
+            Severity::Major
+        );
+        let private_struct = api_item(
+            "t::S",
+            ItemKind::Struct,
+            "struct S { a: u32 }",
+            false,
+            false,
+        );
+        assert_eq!(
+            classify_addition(&field, Some(&private_struct)).0,
+            Severity::Minor
+        );
+
+        let tr = api_item("t::T", ItemKind::Trait, "trait T {}", false, false);
+        let required = api_item("t::T::f", ItemKind::Function, "fn f()", true, false);
+        let provided = api_item("t::T::f", ItemKind::Function, "fn f()", false, false);
+        assert_eq!(classify_addition(&required, Some(&tr)).0, Severity::Major);
+        assert_eq!(classify_addition(&provided, Some(&tr)).0, Severity::Minor);
+        assert_eq!(
+            classify_change(&provided, &required).map(|x| x.0),
+            Some(Severity::Major)
+        );
+        assert_eq!(
+            classify_change(&required, &provided).map(|x| x.0),
+            Some(Severity::Minor)
+        );
+        assert_eq!(classify_change(&provided, &provided), None);
+
+        // Member changes of ADTs are reported (and classified) separately
+        let grown_struct = api_item(
+            "t::S",
+            ItemKind::Struct,
+            "struct S { a: u32, b: u32 }",
+            false,
+            false,
+        );
+        assert_eq!(
+            classify_change(&private_struct, &grown_struct).map(|x| x.0),
+            Some(Severity::Patch)
+        );
+        let generic_struct = api_item(
+            "t::S",
+            ItemKind::Struct,
+            "struct S<T> { a: u32 }",
+            false,
+            false,
+        );
+        assert_eq!(
+            classify_change(&private_struct, &generic_struct).map(|x| x.0),
+            Some(Severity::Major)
+        );
+    }
+
+    fn api_item(
+        path: &str,
+        kind: crate::doc::ItemKind,
+        signature: &str,
+        is_required: bool,
+        is_exhaustive: bool,
+    ) -> ApiItem {
+        ApiItem {
+            path: crate::doc::ItemPath::from_segments(
+                path.split("::").map(str::to_owned).collect(),
+            ),
+            aliases: Vec::new(),
+            kind,
+            signature: signature.to_owned(),
+            is_required,
+            is_exhaustive,
+            is_trait_impl: false,
+            impl_path: None,
+            occurrence: 0,
+        }
+    }
+
+    fn parse_doc(text: &str) -> crate::Result<crate::doc::CrateDoc> {
+        let function = |ty| {
+            format!(
+                r#"{{"sig": {{"inputs": [["x", {{"primitive": "{ty}"}}]], "output": null, "is_c_variadic": false}}, "generics": {{"params": [], "where_predicates": []}}, "header": {{"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}}, "has_body": true}}"#
+            )
+        };
+        let text = text
+            .replace("FN_U32", &function("u32"))
+            .replace("FN_U64", &function("u64"));
+        Ok(crate::doc::CrateDoc::parse(
+            std::path::PathBuf::from(""),
+            &text,
+        )?)
+    }
+}
@@ -0,0 +1,327 @@
+//! On-disk cache of parsed docs
+//!
+//! Parsing a large rustdoc JSON file (e.g., that of `std`) and collecting its public items
+//! takes much longer than answering a query. The cache stores the result of the collection
+//! ([`crate::doc::CrateDoc::index`]) and the signatures of the public functions (for `--sig`
+//! queries) so that later runs only parse the JSON of the docs they actually render. An entry
+//! is used only if the size and modification time of the doc file and the version of siru are
+//! the same as when it was stored.
+
+/// Returns the cache directory (`$XDG_CACHE_HOME/siru` or `$HOME/.cache/siru`)
+pub fn cache_dir() -> Option<std::path::PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => std::path::PathBuf::from(dir),
+        _ => std::path::PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join(env!("CARGO_PKG_NAME")))
+}
+
+/// Identity of a doc file, used to tell whether a cache entry is still valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceKey {
+    pub path: std::path::PathBuf,
+    pub size: u64,
+    /// Modification time as (seconds, nanoseconds) since the Unix epoch
+    pub mtime: (u64, u32),
+}
+
+impl SourceKey {
+    pub fn new(path: &std::path::Path, metadata: &std::fs::Metadata) -> std::io::Result<Self> {
+        let mtime = metadata
+            .modified()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(Self {
+            path: std::path::absolute(path)?,
+            size: metadata.len(),
+            mtime: (mtime.as_secs(), mtime.subsec_nanos()),
+        })
+    }
+
+    /// Returns the file name of the entry for this source (a hash of the path)
+    fn entry_file_name(&self) -> String {
+        // FNV-1a, which is stable across builds unlike `std`'s default hasher
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in self.path.as_os_str().as_encoded_bytes() {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{hash:016x}.json")
+    }
+}
+
+impl nojson::DisplayJson for SourceKey {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("path", &self.path)?;
+            f.member("size", self.size)?;
+            f.member("mtime", [self.mtime.0, u64::from(self.mtime.1)])
+        })
+    }
+}
+
+impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for SourceKey {
+    type Error = nojson::JsonParseError;
+
+    fn try_from(value: nojson::RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let mtime = value.to_member("mtime")?.required()?;
+        let [secs, nanos]: [u64; 2] = mtime.try_into()?;
+        Ok(Self {
+            path: value.to_member("path")?.required()?.try_into()?,
+            size: value.to_member("size")?.required()?.try_into()?,
+            mtime: (
+                secs,
+                nanos
+                    .try_into()
+                    .map_err(|_| mtime.invalid("nanoseconds out of range"))?,
+            ),
+        })
+    }
+}
+
+/// Returns the cached doc for `key`, if there is a valid entry
+///
+/// The JSON of the returned doc is not loaded (see [`crate::doc::CrateDoc::load_json`]).
+pub fn load(key: &SourceKey) -> Option<crate::doc::CrateDoc> {
+    let entry_path = cache_dir()?.join(key.entry_file_name());
+    let text = std::fs::read_to_string(entry_path).ok()?;
+    let json = nojson::RawJson::parse(&text).ok()?;
+    read_entry(key, json.value()).ok().flatten()
+}
+
+fn read_entry(
+    key: &SourceKey,
+    entry: nojson::RawJsonValue<'_, '_>,
+) -> Result<Option<crate::doc::CrateDoc>, nojson::JsonParseError> {
+    let version: String = entry.to_member("version")?.required()?.try_into()?;
+    let source: SourceKey = entry.to_member("source")?.required()?.try_into()?;
+    if version != env!("CARGO_PKG_VERSION") || source != *key {
+        return Ok(None);
+    }
+    let index = entry.to_member("doc")?.required()?;
+    let mut doc = crate::doc::CrateDoc::from_index(key.path.clone(), index)?;
+    let mut function_sigs = std::collections::HashMap::new();
+    for pair in entry.to_member("function_sigs")?.required()?.to_array()? {
+        let [id, sig]: [nojson::RawJsonValue; 2] = pair
+            .to_array()?
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| pair.invalid("expected an [id, signature] pair"))?;
+        function_sigs.insert(id.try_into()?, sig.try_into()?);
+    }
+    doc.function_sigs = Some(function_sigs);
+    Ok(Some(doc))
+}
+
+/// Stores the parsed `doc` read from the file identified by `key`
+pub fn store(key: &SourceKey, doc: &crate::doc::CrateDoc) -> std::io::Result<()> {
+    let dir =
+        cache_dir().ok_or_else(|| std::io::Error::other("cannot determine the cache directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let entry = format_entry(key, doc).map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    // Write to a temporary file first so that concurrent runs never see a partial entry
+    let entry_path = dir.join(key.entry_file_name());
+    let temp_path = entry_path.with_extension(format!("{TEMP_EXTENSION}.{}", std::process::id()));
+    std::fs::write(&temp_path, entry)
+        .and_then(|()| std::fs::rename(&temp_path, &entry_path))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp_path);
+        })
+}
+
+fn format_entry(key: &SourceKey, doc: &crate::doc::CrateDoc) -> crate::Result<String> {
+    let function_sigs = crate::search::FunctionSig::collect(doc)?;
+    let entry = nojson::object(|f| {
+        f.member("version", env!("CARGO_PKG_VERSION"))?;
+        f.member("source", key)?;
+        f.member("doc", doc.index())?;
+        f.member(
+            "function_sigs",
+            nojson::array(|f| {
+                for (id, sig) in &function_sigs {
+                    f.element(nojson::array(|f| {
+                        f.element(id)?;
+                        f.element(sig)
+                    }))?;
+                }
+                Ok(())
+            }),
+        )
+    });
+    Ok(entry.to_string())
+}
+
+/// Extension of temporary files (followed by the process id), which are left behind only if
+/// siru is interrupted while storing an entry
+const TEMP_EXTENSION: &str = "tmp";
+
+/// Summary of a cache entry
+#[derive(Debug)]
+pub struct CacheEntry {
+    pub entry_path: std::path::PathBuf,
+    /// Size of the entry file in bytes
+    pub entry_size: u64,
+    pub version: String,
+    pub source: SourceKey,
+    pub crate_name: String,
+}
+
+impl CacheEntry {
+    /// Returns whether the entry can be used (i.e., the doc file has not changed since)
+    pub fn is_valid(&self) -> bool {
+        self.version == env!("CARGO_PKG_VERSION")
+            && std::fs::metadata(&self.source.path)
+                .and_then(|metadata| SourceKey::new(&self.source.path, &metadata))
+                .is_ok_and(|key| key == self.source)
+    }
+}
+
+/// Returns the entries in the cache directory sorted by source path
+pub fn entries() -> std::io::Result<Vec<CacheEntry>> {
+    let mut entries = Vec::new();
+    for entry_path in entry_paths()? {
+        let Ok(text) = std::fs::read_to_string(&entry_path) else {
+            continue;
+        };
+        let Ok(json) = nojson::RawJson::parse(&text) else {
+            continue;
+        };
+        let read = |entry: nojson::RawJsonValue<'_, '_>| -> Result<_, nojson::JsonParseError> {
+            let doc = entry.to_member("doc")?.required()?;
+            Ok(CacheEntry {
+                entry_size: text.len() as u64,
+                version: entry.to_member("version")?.required()?.try_into()?,
+                source: entry.to_member("source")?.required()?.try_into()?,
+                crate_name: doc.to_member("crate_name")?.required()?.try_into()?,
+                entry_path: entry_path.clone(),
+            })
+        };
+        if let Ok(entry) = read(json.value()) {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| a.source.path.cmp(&b.source.path));
+    Ok(entries)
+}
+
+/// Removes all entries (and leftover temporary files) and returns the number of removed entries
+pub fn clear() -> std::io::Result<usize> {
+    let mut count = 0;
+    for path in dir_file_paths()? {
+        if is_entry_path(&path) {
+            std::fs::remove_file(path)?;
+            count += 1;
+        } else if is_temp_path(&path) {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(count)
+}
+
+fn entry_paths() -> std::io::Result<Vec<std::path::PathBuf>> {
+    let mut paths = dir_file_paths()?;
+    paths.retain(|path| is_entry_path(path));
+    Ok(paths)
+}
+
+fn is_entry_path(path: &std::path::Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Returns whether `path` is a temporary file such as `0123456789abcdef.tmp.42`
+fn is_temp_path(path: &std::path::Path) -> bool {
+    path.file_stem()
+        .and_then(|stem| std::path::Path::new(stem).extension())
+        .is_some_and(|ext| ext == TEMP_EXTENSION)
+}
+
+fn dir_file_paths() -> std::io::Result<Vec<std::path::PathBuf>> {
+    let Some(dir) = cache_dir() else {
+        return Ok(Vec::new());
+    };
+    let read_dir = match std::fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut paths = Vec::new();
+    for entry in read_dir {
+        paths.push(entry?.path());
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn restore_doc_from_index() -> crate::Result<()> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "foo", "visibility": "public", "docs": null, "deprecation": null,
+                  "inner": {"module": {"items": [1, 2]}}},
+            "1": {"id": 1, "name": "Bar", "visibility": "public", "docs": "A bar.", "deprecation": null,
+                  "inner": {"struct": {"kind": "unit", "generics": {"params": [], "where_predicates": []}, "impls": []}}},
+            "2": {"id": 2, "name": null, "visibility": "public", "docs": null, "deprecation": null,
+                  "inner": {"use": {"source": "other::Baz", "name": "Baz", "id": 9, "is_glob": false}}}
+        }, "paths": {"9": {"crate_id": 1, "path": ["other", "Baz"], "kind": "struct"}}}"#;
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from("foo.json"), text)?;
+
+        let index = nojson::Json(doc.index()).to_string();
+        let index = nojson::RawJson::parse(&index)?;
+        let restored =
+            crate::doc::CrateDoc::from_index(std::path::PathBuf::from("foo.json"), index.value())?;
+        assert!(!restored.is_json_loaded());
+        assert_eq!(restored.crate_name, "foo");
+        assert_eq!(restored.public_item_count, doc.public_item_count);
+        assert_eq!(restored.external_reexports.len(), 1);
+        assert_eq!(
+            restored.external_reexports[0].target_path.to_string(),
+            "other::Baz"
+        );
+
+        restored.load_json(text)?;
+        let (path, item) = &restored.show_items[1];
+        assert_eq!(path.to_string(), "foo::Bar");
+        assert_eq!(item.docs(restored.json())?.as_deref(), Some("A bar."));
+        assert_eq!(
+            crate::format_item::format_signature_to_string(&restored, item, false)?,
+            crate::format_item::format_signature_to_string(&doc, &doc.show_items[1].1, false)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn restore_function_sigs() -> crate::Result<()> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "foo", "visibility": "public", "docs": null, "deprecation": null,
+                  "inner": {"module": {"items": [1]}}},
+            "1": {"id": 1, "name": "len", "visibility": "public", "docs": null, "deprecation": null,
+                  "inner": {"function": {"sig": {"inputs": [["s", {"borrowed_ref": {"lifetime": null, "is_mutable": false, "type": {"primitive": "str"}}}]], "output": {"primitive": "usize"}, "is_c_variadic": false},
+                                         "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}}
+        }}"#;
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from("foo.json"), text)?;
+        let key = super::SourceKey {
+            path: std::path::PathBuf::from("foo.json"),
+            size: text.len() as u64,
+            mtime: (1, 2),
+        };
+
+        let entry = super::format_entry(&key, &doc)?;
+        let entry = nojson::RawJson::parse(&entry)?;
+        let restored = super::read_entry(&key, entry.value())?.expect("valid entry");
+        assert!(!restored.is_json_loaded());
+        assert_eq!(restored.function_sigs.as_ref().map(|x| x.len()), Some(1));
+
+        // Signature queries do not need the JSON of restored docs
+        let (_, item) = &restored.show_items[1];
+        for query in ["&str -> usize", "str", "-> String"] {
+            let matcher: crate::search::SignatureMatcher = query.parse().expect("valid query");
+            assert_eq!(
+                matcher.score(&restored, item)?,
+                matcher.score(&doc, &doc.show_items[1].1)?,
+                "{query}"
+            );
+        }
+        Ok(())
+    }
+}
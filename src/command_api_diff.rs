@@ -0,0 +1,155 @@
+pub fn try_run(args: &mut noargs::RawArgs) -> noargs::Result<bool> {
+    if !noargs::cmd("api-diff")
+        .doc("Compare the public APIs of two rustdoc JSON files of a crate")
+        .take(args)
+        .is_present()
+    {
+        return Ok(false);
+    }
+
+    let format: crate::command_main::OutputFormat = noargs::opt("format")
+        .ty("markdown|json|jsonl")
+        .doc("Output format (json: an object with a change array, jsonl: one change object per line)")
+        .default("markdown")
+        .take(args)
+        .then(|a| a.value().parse())?;
+    let fail_on_breaking = noargs::flag("fail-on-breaking")
+        .doc("Exit with status 2 if breaking (semver-major) changes are found")
+        .take(args)
+        .is_present();
+    let old_path: std::path::PathBuf = noargs::arg("OLD_DOC")
+        .doc("Path to the rustdoc JSON file of the old version")
+        .example("old/target/doc/my_crate.json")
+        .take(args)
+        .then(|a| a.value().parse())?;
+    let new_path: std::path::PathBuf = noargs::arg("NEW_DOC")
+        .doc("Path to the rustdoc JSON file of the new version")
+        .example("target/doc/my_crate.json")
+        .take(args)
+        .then(|a| a.value().parse())?;
+
+    if args.metadata().help_mode {
+        return Ok(true);
+    }
+
+    let old_doc = crate::command_main::load_crate_doc(old_path)?;
+    let new_doc = crate::command_main::load_crate_doc(new_path)?;
+    let changes = crate::api_diff::diff(&old_doc, &new_doc)?;
+
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        crate::command_main::OutputFormat::Markdown => {
+            print_markdown(&mut writer, &old_doc, &new_doc, &changes)?
+        }
+        crate::command_main::OutputFormat::Json => {
+            print_json(&mut writer, &old_doc, &new_doc, &changes)?
+        }
+        crate::command_main::OutputFormat::Jsonl => print_jsonl(&mut writer, &changes)?,
+    }
+
+    if fail_on_breaking
+        && crate::api_diff::required_bump(&changes) == Some(crate::api_diff::Severity::Major)
+    {
+        std::process::exit(2);
+    }
+
+    Ok(true)
+}
+
+fn print_markdown<W: std::io::Write>(
+    writer: &mut W,
+    old_doc: &crate::doc::CrateDoc,
+    new_doc: &crate::doc::CrateDoc,
+    changes: &[crate::api_diff::ApiChange],
+) -> crate::Result<()> {
+    use crate::api_diff::ChangeKind;
+
+    writeln!(writer, "# API Diff: `{}`\n", new_doc.crate_name)?;
+    writeln!(writer, "- Old: `{}`", old_doc.path.display())?;
+    writeln!(writer, "- New: `{}`", new_doc.path.display())?;
+    for (change_kind, title) in [
+        (ChangeKind::Removed, "Removed"),
+        (ChangeKind::Changed, "Changed"),
+        (ChangeKind::Added, "Added"),
+    ] {
+        let count = changes.iter().filter(|x| x.change == change_kind).count();
+        writeln!(writer, "- {title}: {count} items")?;
+    }
+    let bump = crate::api_diff::required_bump(changes).map_or("none", |x| x.as_str());
+    writeln!(writer, "- Semver impact: **{bump}**")?;
+    writeln!(writer)?;
+
+    for (change_kind, title) in [
+        (ChangeKind::Removed, "Removed Items"),
+        (ChangeKind::Changed, "Changed Items"),
+        (ChangeKind::Added, "Added Items"),
+    ] {
+        let section: Vec<_> = changes.iter().filter(|x| x.change == change_kind).collect();
+        if section.is_empty() {
+            continue;
+        }
+
+        writeln!(writer, "## {title}\n")?;
+        for change in section {
+            writeln!(
+                writer,
+                "### [{}] `{}`\n",
+                change.kind.as_keyword_str(),
+                change.path
+            )?;
+            writeln!(
+                writer,
+                "**Impact**: {} ({})\n",
+                change.severity.as_str(),
+                change.reason
+            )?;
+            match (&change.old_signature, &change.new_signature) {
+                (Some(old), Some(new)) => {
+                    writeln!(writer, "**Old**:\n\n```rust\n{old}\n```\n")?;
+                    writeln!(writer, "**New**:\n\n```rust\n{new}\n```\n")?;
+                }
+                (Some(signature), None) | (None, Some(signature)) => {
+                    writeln!(writer, "```rust\n{signature}\n```\n")?;
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_json<W: std::io::Write>(
+    writer: &mut W,
+    old_doc: &crate::doc::CrateDoc,
+    new_doc: &crate::doc::CrateDoc,
+    changes: &[crate::api_diff::ApiChange],
+) -> crate::Result<()> {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.object(|f| {
+            f.member("crate", &new_doc.crate_name)?;
+            f.member("old", &old_doc.path)?;
+            f.member("new", &new_doc.path)?;
+            f.member(
+                "semver",
+                crate::api_diff::required_bump(changes).map(|x| x.as_str()),
+            )?;
+            f.member("changes", changes)
+        })
+    });
+    writeln!(writer, "{output}")?;
+    Ok(())
+}
+
+fn print_jsonl<W: std::io::Write>(
+    writer: &mut W,
+    changes: &[crate::api_diff::ApiChange],
+) -> crate::Result<()> {
+    for change in changes {
+        writeln!(writer, "{}", nojson::Json(change))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+pub fn try_run(args: &mut noargs::RawArgs) -> noargs::Result<bool> {
+    if !noargs::cmd("api-snapshot")
+        .doc("Write a sorted one-item-per-line snapshot of a crate's public API")
+        .take(args)
+        .is_present()
+    {
+        return Ok(false);
+    }
+
+    let output_path: std::path::PathBuf = noargs::opt("output")
+        .short('o')
+        .ty("PATH")
+        .doc("Path to the snapshot file")
+        .default("public-api.txt")
+        .take(args)
+        .then(|a| a.value().parse())?;
+    let check = noargs::flag("check")
+        .doc("Compare with the existing snapshot file instead of writing it (fails with a unified diff)")
+        .take(args)
+        .is_present();
+    let doc_path: std::path::PathBuf = noargs::arg("DOC")
+        .doc("Path to the rustdoc JSON file of the crate")
+        .example("target/doc/my_crate.json")
+        .take(args)
+        .then(|a| a.value().parse())?;
+
+    if args.metadata().help_mode {
+        return Ok(true);
+    }
+
+    let doc = crate::command_main::load_crate_doc(doc_path)?;
+    let snapshot = format_snapshot(&doc).map_err(|e| e.set_json_text(doc.json().text()))?;
+
+    if !check {
+        std::fs::write(&output_path, &snapshot)
+            .map_err(|e| format!("failed to write file '{}': {e}", output_path.display()))?;
+        return Ok(true);
+    }
+
+    let committed = std::fs::read_to_string(&output_path)
+        .map_err(|e| format!("failed to read file '{}': {e}", output_path.display()))?;
+    let diff = crate::line_diff::unified_diff(
+        &committed,
+        &snapshot,
+        &output_path.display().to_string(),
+        &format!("{} (current)", doc.crate_name),
+        3,
+    );
+    if !diff.is_empty() {
+        print!("{diff}");
+        eprintln!(
+            "Public API of '{}' differs from '{}' (run without --check to update it)",
+            doc.crate_name,
+            output_path.display()
+        );
+        std::process::exit(1);
+    }
+
+    Ok(true)
+}
+
+/// Formats each public item (and re-export path) as a single `[kind] path: signature` line
+fn format_snapshot(doc: &crate::doc::CrateDoc) -> crate::Result<String> {
+    let mut lines = Vec::new();
+    for item in crate::api_diff::ApiItem::collect(doc)? {
+        let kind = item.kind.as_keyword_str();
+        let signature = item
+            .signature
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push((item.path.to_string(), kind, signature));
+        for alias in &item.aliases {
+            lines.push((alias.to_string(), kind, format!("pub use {};", item.path)));
+        }
+    }
+    lines.sort();
+    lines.dedup();
+
+    let mut snapshot = String::new();
+    for (path, kind, signature) in lines {
+        snapshot.push_str(&format!("[{kind}] {path}: {signature}\n"));
+    }
+    Ok(snapshot)
+}
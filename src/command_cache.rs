@@ -0,0 +1,62 @@
+pub fn try_run(args: &mut noargs::RawArgs) -> noargs::Result<bool> {
+    if !noargs::cmd("cache")
+        .doc("Manage the cache of parsed doc files")
+        .take(args)
+        .is_present()
+    {
+        return Ok(false);
+    }
+
+    let action: CacheAction = noargs::arg("<clear|list>")
+        .doc("Action to perform (clear: remove all entries, list: show the entries)")
+        .example("list")
+        .take(args)
+        .then(|a| a.value().parse())?;
+
+    if args.metadata().help_mode {
+        return Ok(true);
+    }
+
+    let dir = crate::cache::cache_dir().ok_or("cannot determine the cache directory")?;
+    match action {
+        CacheAction::Clear => {
+            let count = crate::cache::clear()
+                .map_err(|e| format!("failed to clear cache '{}': {e}", dir.display()))?;
+            println!("Removed {count} cache entries from {}", dir.display());
+        }
+        CacheAction::List => {
+            let entries = crate::cache::entries()
+                .map_err(|e| format!("failed to read cache '{}': {e}", dir.display()))?;
+            println!("Cache directory: {}", dir.display());
+            for entry in &entries {
+                println!(
+                    "- `{}`: {} ({} bytes{})",
+                    entry.crate_name,
+                    entry.source.path.display(),
+                    entry.entry_size,
+                    if entry.is_valid() { "" } else { ", stale" }
+                );
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CacheAction {
+    Clear,
+    List,
+}
+
+impl std::str::FromStr for CacheAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clear" => Ok(Self::Clear),
+            "list" => Ok(Self::List),
+            _ => Err("invalid cache action: must be one of clear or list".to_owned()),
+        }
+    }
+}
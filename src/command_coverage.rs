@@ -0,0 +1,233 @@
+pub fn try_run(args: &mut noargs::RawArgs) -> noargs::Result<bool> {
+    if !noargs::cmd("coverage")
+        .doc("Report documentation coverage of public items per crate, module and kind")
+        .take(args)
+        .is_present()
+    {
+        return Ok(false);
+    }
+
+    let doc_paths = crate::command_main::take_doc_paths(args)?;
+    let target_crates = crate::command_main::take_target_crates(args);
+    let threshold: Option<f64> = noargs::opt("threshold")
+        .ty("PERCENTAGE")
+        .doc("Exit with status 1 if the coverage of any crate is below this percentage")
+        .take(args)
+        .present_and_then(|a| a.value().parse())?;
+    let format: crate::command_main::OutputFormat = noargs::opt("format")
+        .ty("markdown|json|jsonl")
+        .doc("Output format (json: an array of crate objects, jsonl: one crate object per line)")
+        .default("markdown")
+        .take(args)
+        .then(|a| a.value().parse())?;
+
+    if args.metadata().help_mode {
+        return Ok(true);
+    }
+
+    let doc_paths = crate::command_main::DocPaths::resolve(doc_paths)?;
+    let target_crates = doc_paths.crate_names(target_crates);
+    let docs =
+        crate::command_main::load_crate_docs(&doc_paths.paths, &target_crates, false, false)?;
+    let mut coverages = Vec::new();
+    for doc in &docs {
+        if !target_crates.is_empty() && !target_crates.contains(&doc.crate_name) {
+            continue;
+        }
+        let coverage = crate::coverage::CrateCoverage::new(doc)
+            .map_err(|e| e.set_json_text(doc.json().text()))?;
+        coverages.push(coverage);
+    }
+
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        crate::command_main::OutputFormat::Markdown => print_markdown(&mut writer, &coverages)?,
+        crate::command_main::OutputFormat::Json => print_json(&mut writer, &coverages)?,
+        crate::command_main::OutputFormat::Jsonl => print_jsonl(&mut writer, &coverages)?,
+    }
+
+    if let Some(threshold) = threshold {
+        let failed: Vec<_> = coverages
+            .iter()
+            .filter(|x| x.by_kind.total().percentage() < threshold)
+            .collect();
+        if !failed.is_empty() {
+            for coverage in failed {
+                eprintln!(
+                    "Documentation coverage of '{}' is {:.1}% (below the threshold {threshold}%)",
+                    coverage.crate_name,
+                    coverage.by_kind.total().percentage()
+                );
+            }
+            std::process::exit(1);
+        }
+    }
+
+    Ok(true)
+}
+
+fn print_json<W: std::io::Write>(
+    writer: &mut W,
+    coverages: &[crate::coverage::CrateCoverage],
+) -> crate::Result<()> {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.array(|f| f.elements(coverages.iter().map(CoverageJson)))
+    });
+    writeln!(writer, "{output}")?;
+    Ok(())
+}
+
+fn print_jsonl<W: std::io::Write>(
+    writer: &mut W,
+    coverages: &[crate::coverage::CrateCoverage],
+) -> crate::Result<()> {
+    for coverage in coverages {
+        writeln!(writer, "{}", nojson::Json(CoverageJson(coverage)))?;
+    }
+    Ok(())
+}
+
+struct CoverageJson<'a>(&'a crate::coverage::CrateCoverage);
+
+impl nojson::DisplayJson for CoverageJson<'_> {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        let coverage = self.0;
+        f.object(|f| {
+            f.member("crate", &coverage.crate_name)?;
+            f.member("total", coverage.by_kind.total())?;
+            f.member("by_kind", &coverage.by_kind)?;
+            f.member(
+                "by_module",
+                nojson::json(|f| {
+                    f.object(|f| {
+                        for (path, counts) in &coverage.by_module {
+                            f.member(
+                                path,
+                                nojson::json(|f| {
+                                    f.object(|f| {
+                                        f.member("total", counts.total())?;
+                                        f.member("by_kind", counts)
+                                    })
+                                }),
+                            )?;
+                        }
+                        Ok(())
+                    })
+                }),
+            )?;
+            f.member(
+                "undocumented",
+                nojson::json(|f| {
+                    f.array(|f| {
+                        for (path, kind) in &coverage.undocumented {
+                            f.element(nojson::json(|f| {
+                                f.object(|f| {
+                                    f.member("path", path.to_string())?;
+                                    f.member("kind", kind.as_keyword_str())
+                                })
+                            }))?;
+                        }
+                        Ok(())
+                    })
+                }),
+            )
+        })
+    }
+}
+
+fn print_markdown<W: std::io::Write>(
+    writer: &mut W,
+    coverages: &[crate::coverage::CrateCoverage],
+) -> crate::Result<()> {
+    writeln!(writer, "# Documentation Coverage\n")?;
+    print_table_header(writer, "Crate")?;
+    for coverage in coverages {
+        let name = format!("`{}`", coverage.crate_name);
+        print_table_row(writer, &name, coverage.by_kind.total())?;
+    }
+    writeln!(writer)?;
+
+    for coverage in coverages {
+        writeln!(writer, "# Crate Coverage: `{}`\n", coverage.crate_name)?;
+
+        writeln!(writer, "## By Kind\n")?;
+        print_table_header(writer, "Kind")?;
+        for (kind, counts) in &coverage.by_kind.0 {
+            print_table_row(writer, kind.as_str(), *counts)?;
+        }
+        writeln!(writer)?;
+
+        writeln!(writer, "## By Module\n")?;
+        for (path, counts) in &coverage.by_module {
+            let total = counts.total();
+            writeln!(
+                writer,
+                "- `{path}`: {}/{} ({:.1}%, {} with examples)",
+                total.documented,
+                total.total,
+                total.percentage(),
+                total.with_examples
+            )?;
+            for (kind, counts) in &counts.0 {
+                writeln!(
+                    writer,
+                    "  - {}: {}/{}",
+                    kind.as_str(),
+                    counts.documented,
+                    counts.total
+                )?;
+            }
+        }
+        writeln!(writer)?;
+
+        if !coverage.undocumented.is_empty() {
+            writeln!(writer, "## Undocumented Items\n")?;
+            let max_kind_len = coverage
+                .undocumented
+                .iter()
+                .map(|(_, kind)| kind.as_keyword_str().len())
+                .max()
+                .unwrap_or(0);
+            for (path, kind) in &coverage.undocumented {
+                writeln!(
+                    writer,
+                    "- [{:<width$}] `{}`",
+                    kind.as_keyword_str(),
+                    path,
+                    width = max_kind_len
+                )?;
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_table_header<W: std::io::Write>(writer: &mut W, name: &str) -> crate::Result<()> {
+    writeln!(
+        writer,
+        "| {name} | Documented | Total | Coverage | With Examples |"
+    )?;
+    writeln!(writer, "|---|--:|--:|--:|--:|")?;
+    Ok(())
+}
+
+fn print_table_row<W: std::io::Write>(
+    writer: &mut W,
+    name: &str,
+    counts: crate::coverage::Counts,
+) -> crate::Result<()> {
+    writeln!(
+        writer,
+        "| {name} | {} | {} | {:.1}% | {} |",
+        counts.documented,
+        counts.total,
+        counts.percentage(),
+        counts.with_examples
+    )?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+pub fn try_run(args: &mut noargs::RawArgs) -> noargs::Result<bool> {
+    if !noargs::cmd("lint")
+        .doc("Check public items for common documentation problems")
+        .take(args)
+        .is_present()
+    {
+        return Ok(false);
+    }
+
+    let doc_paths = crate::command_main::take_doc_paths(args)?;
+    let target_crates = crate::command_main::take_target_crates(args);
+    let format: crate::command_main::OutputFormat = noargs::opt("format")
+        .ty("markdown|json|jsonl")
+        .doc(
+            "Output format (json: an array of finding objects, jsonl: one finding object per line)",
+        )
+        .default("markdown")
+        .take(args)
+        .then(|a| a.value().parse())?;
+
+    if args.metadata().help_mode {
+        return Ok(true);
+    }
+
+    let doc_paths = crate::command_main::DocPaths::resolve(doc_paths)?;
+    let target_crates = doc_paths.crate_names(target_crates);
+    let docs =
+        crate::command_main::load_crate_docs(&doc_paths.paths, &target_crates, false, false)?;
+    let mut results = Vec::new();
+    for doc in &docs {
+        if !target_crates.is_empty() && !target_crates.contains(&doc.crate_name) {
+            continue;
+        }
+        let findings = crate::lint::lint(doc).map_err(|e| e.set_json_text(doc.json().text()))?;
+        results.push((doc.crate_name.as_str(), findings));
+    }
+
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        crate::command_main::OutputFormat::Markdown => print_markdown(&mut writer, &results)?,
+        crate::command_main::OutputFormat::Json => print_json(&mut writer, &results)?,
+        crate::command_main::OutputFormat::Jsonl => print_jsonl(&mut writer, &results)?,
+    }
+
+    Ok(true)
+}
+
+fn print_json<W: std::io::Write>(
+    writer: &mut W,
+    results: &[(&str, Vec<crate::lint::Finding>)],
+) -> crate::Result<()> {
+    let output = nojson::json(|f| {
+        f.set_indent_size(2);
+        f.set_spacing(true);
+        f.array(|f| f.elements(finding_jsons(results)))
+    });
+    writeln!(writer, "{output}")?;
+    Ok(())
+}
+
+fn print_jsonl<W: std::io::Write>(
+    writer: &mut W,
+    results: &[(&str, Vec<crate::lint::Finding>)],
+) -> crate::Result<()> {
+    for finding in finding_jsons(results) {
+        writeln!(writer, "{}", nojson::Json(finding))?;
+    }
+    Ok(())
+}
+
+fn finding_jsons<'a>(
+    results: &'a [(&'a str, Vec<crate::lint::Finding>)],
+) -> impl Iterator<Item = FindingJson<'a>> {
+    results.iter().flat_map(|(crate_name, findings)| {
+        findings
+            .iter()
+            .map(|finding| FindingJson(crate_name, finding))
+    })
+}
+
+/// A finding with the name of the crate it was found in
+struct FindingJson<'a>(&'a str, &'a crate::lint::Finding);
+
+impl nojson::DisplayJson for FindingJson<'_> {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        let FindingJson(crate_name, finding) = self;
+        f.object(|f| {
+            f.member("crate", crate_name)?;
+            f.member("lint", finding.lint.as_str())?;
+            f.member("path", finding.path.to_string())?;
+            f.member("kind", finding.kind.as_keyword_str())?;
+            f.member("message", &finding.message)?;
+            f.member("span", &finding.span)
+        })
+    }
+}
+
+fn print_markdown<W: std::io::Write>(
+    writer: &mut W,
+    results: &[(&str, Vec<crate::lint::Finding>)],
+) -> crate::Result<()> {
+    for (crate_name, findings) in results {
+        writeln!(writer, "# Lint Findings: `{crate_name}`\n")?;
+        if findings.is_empty() {
+            writeln!(writer, "No problems found.\n")?;
+            continue;
+        }
+        for finding in findings {
+            write!(
+                writer,
+                "- [{}] `{}`: {}",
+                finding.lint.as_str(),
+                finding.path,
+                finding.message
+            )?;
+            if let Some(span) = &finding.span {
+                write!(writer, " (`{span}`)")?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
@@ -1,23 +1,6 @@
 pub fn run(args: &mut noargs::RawArgs) -> noargs::Result<()> {
-    let doc_paths: Vec<std::path::PathBuf> = noargs::opt("doc-path")
-        .short('d')
-        .ty("PATH[:PATH]*")
-        .doc("Path(s) to doc files or dirs containing *.json files, separated by colons")
-        .env("SIRU_DOC_PATH")
-        .default("target/doc/")
-        .take(args)
-        .then(|a| a.value().split(':').map(|a| a.parse()).collect())?;
-
-    let mut target_crates = std::collections::HashSet::new();
-    while let Some(a) = noargs::opt("crate")
-        .short('c')
-        .ty("CRATE_NAME")
-        .doc("Filter to specific crate(s) by name (can be specified multiple times)")
-        .take(args)
-        .present()
-    {
-        target_crates.insert(a.value().to_owned());
-    }
+    let doc_paths = take_doc_paths(args)?;
+    let target_crates = take_target_crates(args);
 
     let mut target_kinds = std::collections::HashSet::new();
     while let Some(kinds) = noargs::opt("kind")
@@ -42,81 +25,547 @@ pub fn run(args: &mut noargs::RawArgs) -> noargs::Result<()> {
             .doc("Print inner JSON representation before item signature")
             .take(args)
             .is_present(),
+        show_hidden_lines: noargs::flag("show-hidden-lines")
+            .doc("Keep doctest lines hidden by rustdoc (`# ...`) in code blocks")
+            .take(args)
+            .is_present(),
+        width: noargs::opt("width")
+            .ty("COLUMNS")
+            .doc("Break signature lines longer than this across multiple lines")
+            .default("100")
+            .take(args)
+            .then(|a| a.value().parse())?,
         verbose: noargs::flag("verbose")
             .doc("Enable verbose output")
             .take(args)
             .is_present(),
+        format: noargs::opt("format")
+            .ty("markdown|json|jsonl")
+            .doc("Output format (json: an array of item objects, jsonl: one item object per line)")
+            .default("markdown")
+            .take(args)
+            .then(|a| a.value().parse())?,
+        strict: noargs::flag("strict")
+            .doc("Report rustdoc JSON that cannot be rendered as an error instead of printing it raw")
+            .take(args)
+            .is_present(),
     };
 
+    let use_cache = !noargs::flag("no-cache")
+        .doc("Parse all doc files instead of using the cache of parsed docs")
+        .env("SIRU_NO_CACHE")
+        .take(args)
+        .is_present();
+
+    let substring_only = noargs::flag("substring")
+        .doc("Match ITEM_PATH_PART as case-sensitive substrings without ranking")
+        .take(args)
+        .is_present();
+
+    let signature_matcher: Option<crate::search::SignatureMatcher> = noargs::opt("sig")
+        .ty("SIGNATURE")
+        .doc(concat!(
+            "Filter functions to those matching a simplified signature (e.g., \"&str -> Result<_, _>\")\n",
+            "Single uppercase letters and `Self` match loosely as generic parameters"
+        ))
+        .take(args)
+        .present_and_then(|a| a.value().parse())?;
+
     let mut target_path_parts = Vec::new();
     while let Some(part) = noargs::arg("[ITEM_PATH_PART]...")
-        .doc("Filter items to only those having all specified path parts")
+        .doc("Filter items to those matching all specified path parts (best matches first)")
         .take(args)
         .present_and_then(|a| a.value().parse::<String>())?
     {
         target_path_parts.push(part);
     }
+    let matcher = crate::search::PathMatcher::new(target_path_parts, substring_only);
 
     if args.metadata().help_mode {
         return Ok(());
     }
 
-    let doc_file_paths = collect_doc_file_paths(&doc_paths)?;
+    let doc_paths = DocPaths::resolve(doc_paths)?;
+    let target_crates = doc_paths.crate_names(target_crates);
+    let mut docs = load_crate_docs(
+        &doc_paths.paths,
+        &target_crates,
+        show_options.verbose,
+        use_cache,
+    )?;
+    if let Some(workspace) = &doc_paths.workspace {
+        // Crates of the workspace members come first, in the order of the members
+        docs.sort_by_key(|doc| {
+            workspace
+                .member_index(&doc.crate_name)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    let selected_docs: Vec<_> = docs
+        .iter()
+        .filter(|doc| target_crates.is_empty() || target_crates.contains(&doc.crate_name))
+        .collect();
+    // The JSON of cached docs is parsed only if their items are shown
+    // (or ranked by signature without cached signatures)
+    if signature_matcher.is_some() {
+        load_docs_json(
+            selected_docs
+                .iter()
+                .flat_map(|doc| std::iter::once(*doc).chain(source_docs(doc, &docs)))
+                .filter(|doc| doc.function_sigs.is_none()),
+        )?;
+    }
+    let crates = crate::parallel::map(&selected_docs, |doc| {
+        let mut show_crate = ShowCrate::new(doc, &docs);
+        if !target_kinds.is_empty() {
+            show_crate
+                .items
+                .retain(|x| target_kinds.contains(&x.item.kind));
+        } else {
+            show_crate
+                .items
+                .retain(|x| x.item.kind.is_shown_by_default());
+        }
+        if !matcher.is_empty() || signature_matcher.is_some() {
+            show_crate
+                .rank_items(&matcher, signature_matcher.as_ref())
+                .map_err(|e| e.to_string())?;
+        }
+        Ok::<_, String>(show_crate)
+    })
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+    load_docs_json(
+        crates
+            .iter()
+            .filter(|c| !c.items.is_empty())
+            .flat_map(|c| std::iter::once(c.doc).chain(c.items.iter().map(|x| x.doc))),
+    )?;
+
     if show_options.verbose {
+        for show_crate in &crates {
+            eprintln!("Items in crate '{}':", show_crate.doc.crate_name);
+            for x in &show_crate.items {
+                let inner = x.item.inner(x.doc.json());
+                eprintln!("  [{}] {}: {}", x.item.kind, x.path, inner);
+            }
+        }
+    }
+
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+    print_output(&mut writer, &crates, &show_options)?;
+
+    Ok(())
+}
+
+struct ShowOptions {
+    show_inner_json: bool,
+    show_hidden_lines: bool,
+    width: usize,
+    verbose: bool,
+    format: OutputFormat,
+    strict: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Markdown,
+    Json,
+    Jsonl,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            _ => Err("invalid output format: must be one of markdown, json or jsonl".to_owned()),
+        }
+    }
+}
+
+/// Items of a crate to show, including those re-exported from other crates
+struct ShowCrate<'a> {
+    doc: &'a crate::doc::CrateDoc,
+    public_item_count: usize,
+    items: Vec<ShowItem<'a>>,
+}
+
+impl<'a> ShowCrate<'a> {
+    fn new(doc: &'a crate::doc::CrateDoc, docs: &'a [crate::doc::CrateDoc]) -> Self {
+        let mut items: Vec<_> = doc
+            .show_items
+            .iter()
+            .map(|(path, item)| ShowItem {
+                path: path.clone(),
+                doc,
+                item,
+                origin: None,
+                score: None,
+            })
+            .collect();
+
+        let mut public_item_count = doc.public_item_count;
+        let mut known_items: std::collections::HashSet<_> = items
+            .iter()
+            .map(|x| (x.path.clone(), x.item.kind))
+            .collect();
+        for reexport in &doc.external_reexports {
+            let Some(source_doc) = docs.iter().find(|d| d.crate_name == reexport.crate_name) else {
+                continue;
+            };
+            for (path, item) in source_doc.resolve_reexport(reexport) {
+                if !known_items.insert((path.clone(), item.kind)) {
+                    continue;
+                }
+                if item.kind.is_shown_by_default() {
+                    public_item_count += 1;
+                }
+                let origin = source_doc.canonical_path(item.id);
+                items.push(ShowItem {
+                    path,
+                    doc: source_doc,
+                    item,
+                    origin,
+                    score: None,
+                });
+            }
+        }
+
+        Self {
+            doc,
+            public_item_count,
+            items,
+        }
+    }
+}
+
+impl ShowCrate<'_> {
+    /// Drops items not matching the matchers and sorts the rest best-first
+    ///
+    /// With `--substring`, the remaining items keep their traversal order and are not scored.
+    fn rank_items(
+        &mut self,
+        matcher: &crate::search::PathMatcher,
+        signature_matcher: Option<&crate::search::SignatureMatcher>,
+    ) -> crate::Result<()> {
+        let mut scored = Vec::new();
+        for x in std::mem::take(&mut self.items) {
+            // Items re-exported elsewhere also match by their alias paths
+            let Some(path_score) = std::iter::once(&x.path)
+                .chain(x.doc.aliases(x.item))
+                .filter_map(|path| matcher.score(path))
+                .max()
+            else {
+                continue;
+            };
+            let signature_score = match signature_matcher {
+                Some(m) => match m
+                    .score(x.doc, x.item)
+                    .map_err(|e| e.set_json_text(x.doc.json().text()))?
+                {
+                    Some(score) => score,
+                    None => continue,
+                },
+                None => 0,
+            };
+            if matcher.is_substring_only() {
+                scored.push(x);
+                continue;
+            }
+            scored.push(ShowItem {
+                score: Some(path_score + signature_score),
+                ..x
+            });
+        }
+        if matcher.is_substring_only() {
+            self.items = scored;
+            return Ok(());
+        }
+        scored.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.path.segments().len().cmp(&b.path.segments().len()))
+        });
+        self.items = scored;
+        Ok(())
+    }
+}
+
+struct ShowItem<'a> {
+    path: crate::doc::ItemPath,
+    doc: &'a crate::doc::CrateDoc,
+    item: &'a crate::doc::Item,
+    /// Canonical path in the defining crate (for items re-exported from other crates)
+    origin: Option<&'a crate::doc::ItemPath>,
+    /// Relevance to the queries (`None` if the items are not ranked)
+    score: Option<u32>,
+}
+
+/// Returns the items of `crates` in output order
+///
+/// Ranked items are ordered by score across crates (ties keep the crate order), so that
+/// an exact match in a later crate is not listed after weak matches in earlier ones.
+fn output_items<'a>(crates: &'a [ShowCrate<'a>]) -> Vec<(&'a ShowCrate<'a>, &'a ShowItem<'a>)> {
+    let mut items: Vec<_> = crates
+        .iter()
+        .flat_map(|c| c.items.iter().map(move |x| (c, x)))
+        .collect();
+    items.sort_by_key(|(_, x)| std::cmp::Reverse(x.score));
+    items
+}
+
+/// Returns the paths given by `--doc-path` (`None` if not given; see [`DocPaths::resolve`])
+pub fn take_doc_paths(
+    args: &mut noargs::RawArgs,
+) -> noargs::Result<Option<Vec<std::path::PathBuf>>> {
+    noargs::opt("doc-path")
+        .short('d')
+        .ty("PATH[:PATH]*")
+        .doc(concat!(
+            "Path(s) to doc files or dirs containing *.json files, separated by colons\n",
+            "Defaults to the doc dir in the target dir of the Cargo workspace (or target/doc/)"
+        ))
+        .env("SIRU_DOC_PATH")
+        .take(args)
+        .present_and_then(|a| a.value().split(':').map(|a| a.parse()).collect())
+}
+
+/// Paths to load docs from
+#[derive(Debug)]
+pub struct DocPaths {
+    pub paths: Vec<std::path::PathBuf>,
+    /// Workspace containing the current directory (only looked up if no path is given)
+    pub workspace: Option<crate::workspace::Workspace>,
+}
+
+impl DocPaths {
+    /// Uses the given paths, or finds the doc directory of the current Cargo workspace
+    pub fn resolve(doc_paths: Option<Vec<std::path::PathBuf>>) -> noargs::Result<Self> {
+        if let Some(paths) = doc_paths {
+            return Ok(Self {
+                paths,
+                workspace: None,
+            });
+        }
+
+        let current_dir = std::env::current_dir()
+            .map_err(|e| format!("failed to get the current directory: {e}"))?;
+        let workspace = crate::workspace::Workspace::discover(&current_dir).unwrap_or_else(|e| {
+            eprintln!("Warning: {e}");
+            None
+        });
+        let paths = vec![
+            workspace
+                .as_ref()
+                .map_or_else(|| "target/doc/".into(), |ws| ws.doc_dir()),
+        ];
+        Ok(Self { paths, workspace })
+    }
+
+    /// Maps the names given by `--crate` to crate names
+    /// (package names of workspace members are accepted, as are dashes in place of underscores)
+    pub fn crate_names(
+        &self,
+        target_crates: std::collections::HashSet<String>,
+    ) -> std::collections::HashSet<String> {
+        target_crates
+            .into_iter()
+            .map(|name| {
+                self.workspace
+                    .as_ref()
+                    .and_then(|ws| ws.crate_name(&name))
+                    .map_or_else(|| name.replace('-', "_"), |name| name.to_owned())
+            })
+            .collect()
+    }
+}
+
+pub fn take_target_crates(args: &mut noargs::RawArgs) -> std::collections::HashSet<String> {
+    let mut target_crates = std::collections::HashSet::new();
+    while let Some(a) = noargs::opt("crate")
+        .short('c')
+        .ty("CRATE_NAME")
+        .doc("Filter to specific crate(s) by crate or package name (can be specified multiple times)")
+        .take(args)
+        .present()
+    {
+        target_crates.insert(a.value().to_owned());
+    }
+    target_crates
+}
+
+/// Loads the docs of the crates found in `doc_paths` (the first one wins for duplicate crates)
+///
+/// If `target_crates` is not empty, only the files of those crates and of the crates they
+/// re-export items from are parsed; the crate names of the other files are found by
+/// [`crate::doc::CrateDoc::peek_crate_name`].
+///
+/// If `use_cache` is `true`, docs may be restored from [`crate::cache`] without their JSON,
+/// which then has to be loaded by [`load_doc_json`] before use.
+pub fn load_crate_docs(
+    doc_paths: &[std::path::PathBuf],
+    target_crates: &std::collections::HashSet<String>,
+    verbose: bool,
+    use_cache: bool,
+) -> noargs::Result<Vec<crate::doc::CrateDoc>> {
+    let doc_file_paths = collect_doc_file_paths(doc_paths)?;
+    if verbose {
         eprintln!("Documentation file paths:");
         for path in &doc_file_paths {
             eprintln!("  {}", path.display());
         }
     }
 
+    // Crate names are `None` until the files are loaded if they cannot be found cheaply
+    let crate_names = if target_crates.is_empty() {
+        vec![None; doc_file_paths.len()]
+    } else {
+        crate::parallel::map(&doc_file_paths, |path| peek_crate_name(path))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let mut loaded = vec![false; doc_file_paths.len()];
+
     let mut docs = Vec::new();
     let mut known_crates = std::collections::HashSet::new();
-    for path in doc_file_paths {
-        let text = std::fs::read_to_string(&path)
-            .map_err(|e| format!("failed to read file '{}': {e}", path.display()))?;
-        let mut doc = crate::doc::CrateDoc::parse(path, &text)
-            .map_err(|e| crate::json::format_parse_error(&text, &e))?;
+    let mut wanted_crates = target_crates.clone();
+    // The second pass loads the crates whose items are re-exported by the target crates
+    for pass in 0..2 {
+        let mut pending_crates = std::collections::HashSet::new();
+        let mut indices = Vec::new();
+        for (i, crate_name) in crate_names.iter().enumerate() {
+            let is_wanted = match crate_name {
+                Some(name) => wanted_crates.contains(name),
+                None => pass == 0,
+            };
+            if loaded[i] || !is_wanted {
+                continue;
+            }
+            loaded[i] = true;
 
-        if !target_crates.is_empty() && !target_crates.contains(&doc.crate_name) {
-            continue;
-        }
-        if !known_crates.insert(doc.crate_name.clone()) {
-            if show_options.verbose {
-                eprintln!("Warning: duplicate crate '{}' ignored", doc.crate_name);
+            if let Some(name) = crate_name
+                && (known_crates.contains(name) || !pending_crates.insert(name))
+            {
+                if verbose {
+                    eprintln!("Warning: duplicate crate '{name}' ignored");
+                }
+                continue;
             }
-            continue;
+            indices.push(i);
         }
-        if !target_kinds.is_empty() {
-            doc.show_items
-                .retain(|(_, item)| target_kinds.contains(&item.kind));
-        }
-        if !target_path_parts.is_empty() {
-            doc.show_items.retain(|(path, _)| {
-                let path = path.to_string();
-                target_path_parts.iter().all(|part| path.contains(part))
-            });
-        }
-        if show_options.verbose {
-            eprintln!("Items in crate '{}':", doc.crate_name);
-            for (path, item) in &doc.show_items {
-                let inner = item.inner(&doc.json);
-                eprintln!("  [{}] {}: {}", item.kind, path, inner);
+
+        let results = crate::parallel::map(&indices, |&i| {
+            let path = doc_file_paths[i].clone();
+            if use_cache {
+                load_cached_crate_doc(path, verbose)
+            } else {
+                load_crate_doc(path)
             }
+        });
+        for (i, result) in indices.into_iter().zip(results) {
+            let doc = result?;
+            if !known_crates.insert(doc.crate_name.clone()) {
+                if verbose {
+                    eprintln!("Warning: duplicate crate '{}' ignored", doc.crate_name);
+                }
+                continue;
+            }
+            docs.push((i, doc));
         }
 
-        docs.push(doc);
+        wanted_crates = docs
+            .iter()
+            .filter(|(_, doc)| target_crates.contains(&doc.crate_name))
+            .flat_map(|(_, doc)| &doc.external_reexports)
+            .map(|reexport| reexport.crate_name.clone())
+            .collect();
     }
 
-    let stdout = std::io::stdout();
-    let mut writer = stdout.lock();
-    print_output(&mut writer, &docs, &show_options)?;
+    docs.sort_by_key(|(i, _)| *i);
+    Ok(docs.into_iter().map(|(_, doc)| doc).collect())
+}
 
-    Ok(())
+/// Finds the crate name of a rustdoc JSON file without parsing it (`None` if not found)
+fn peek_crate_name(path: &std::path::Path) -> Result<Option<String>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read file '{}': {e}", path.display()))?;
+    Ok(crate::doc::CrateDoc::peek_crate_name(&text))
 }
 
-struct ShowOptions {
-    show_inner_json: bool,
+/// Reads and parses a rustdoc JSON file
+pub fn load_crate_doc(path: std::path::PathBuf) -> Result<crate::doc::CrateDoc, String> {
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read file '{}': {e}", path.display()))?;
+    crate::doc::CrateDoc::parse(path, &text).map_err(|e| crate::json::format_parse_error(&text, &e))
+}
+
+/// Restores a doc from the cache, or reads and parses it and stores it to the cache
+fn load_cached_crate_doc(
+    path: std::path::PathBuf,
     verbose: bool,
+) -> Result<crate::doc::CrateDoc, String> {
+    // The metadata is taken before reading so that a concurrently modified file is not cached
+    // as its older version
+    let key = std::fs::metadata(&path)
+        .and_then(|metadata| crate::cache::SourceKey::new(&path, &metadata))
+        .map_err(|e| format!("failed to read file '{}': {e}", path.display()))?;
+    if let Some(doc) = crate::cache::load(&key) {
+        if verbose {
+            eprintln!("Loaded '{}' from cache", path.display());
+        }
+        return Ok(doc);
+    }
+
+    let doc = load_crate_doc(path)?;
+    if let Err(e) = crate::cache::store(&key, &doc)
+        && verbose
+    {
+        eprintln!("Warning: failed to cache '{}': {e}", doc.path.display());
+    }
+    Ok(doc)
+}
+
+/// Returns the docs of the crates that `doc` re-exports items from
+fn source_docs<'a>(
+    doc: &'a crate::doc::CrateDoc,
+    docs: &'a [crate::doc::CrateDoc],
+) -> impl Iterator<Item = &'a crate::doc::CrateDoc> {
+    doc.external_reexports
+        .iter()
+        .filter_map(|reexport| docs.iter().find(|d| d.crate_name == reexport.crate_name))
+}
+
+/// Loads the JSON of docs restored from the cache, each once and on multiple threads
+///
+/// Loading is done before (or after) the docs are shared with parallel workers,
+/// so that the same file is never read and parsed twice.
+fn load_docs_json<'a>(docs: impl Iterator<Item = &'a crate::doc::CrateDoc>) -> Result<(), String> {
+    let mut unloaded_docs: Vec<&crate::doc::CrateDoc> = Vec::new();
+    for doc in docs {
+        if !doc.is_json_loaded() && !unloaded_docs.iter().any(|d| std::ptr::eq(*d, doc)) {
+            unloaded_docs.push(doc);
+        }
+    }
+    crate::parallel::map(&unloaded_docs, |doc| load_doc_json(doc))
+        .into_iter()
+        .collect()
+}
+
+/// Reads and parses the JSON of a doc restored from the cache (does nothing if it is loaded)
+pub fn load_doc_json(doc: &crate::doc::CrateDoc) -> Result<(), String> {
+    if doc.is_json_loaded() {
+        return Ok(());
+    }
+    let text = std::fs::read_to_string(&doc.path)
+        .map_err(|e| format!("failed to read file '{}': {e}", doc.path.display()))?;
+    doc.load_json(&text)
+        .map_err(|e| crate::json::format_parse_error(&text, &e))
 }
 
 fn collect_doc_file_paths(
@@ -153,198 +602,515 @@ fn collect_doc_file_paths(
 
 fn print_output<W: std::io::Write>(
     writer: &mut W,
-    docs: &[crate::doc::CrateDoc],
+    crates: &[ShowCrate],
     show_options: &ShowOptions,
 ) -> crate::Result<()> {
-    print_summary(writer, docs, show_options)?;
-    for doc in docs {
-        if doc.show_items.is_empty() {
-            continue;
+    if show_options.format != OutputFormat::Markdown {
+        return print_json_output(writer, crates, show_options);
+    }
+
+    let items = output_items(crates);
+
+    // Intra-doc links to items in the output point to the anchors of their headings
+    let mut anchors = std::collections::HashMap::new();
+    for (_, x) in &items {
+        let anchor = crate::markdown::heading_anchor(&detail_heading(x));
+        for path in std::iter::once(&x.path)
+            .chain(x.origin)
+            .chain(x.doc.aliases(x.item))
+        {
+            anchors
+                .entry(path.clone())
+                .or_insert_with(|| anchor.clone());
+        }
+    }
+
+    print_summary(writer, crates, &items)?;
+
+    // Details are rendered in parallel and written in order, a batch at a time
+    for batch in items.chunks(OUTPUT_BATCH_SIZE) {
+        let details = crate::parallel::map(batch, |(_, x)| {
+            let mut detail = Vec::new();
+            print_detail(&mut detail, x, &anchors, show_options)
+                .map_err(|e| e.set_json_text(x.doc.json().text()))?;
+            Ok::<_, crate::Error>(detail)
+        });
+        for detail in details {
+            writer.write_all(&detail?)?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Number of items rendered in parallel before they are written
+///
+/// Output starts as soon as the first batch is rendered (e.g., when piped to a pager).
+const OUTPUT_BATCH_SIZE: usize = 64;
+
+fn print_json_output<W: std::io::Write>(
+    writer: &mut W,
+    crates: &[ShowCrate],
+    show_options: &ShowOptions,
+) -> crate::Result<()> {
+    let format = show_options.format;
+    let items = output_items(crates);
+    let mut records = Vec::new();
+    for batch in items.chunks(OUTPUT_BATCH_SIZE) {
+        let results = crate::parallel::map(batch, |(show_crate, x)| {
+            JsonItemRecord::new(show_crate, x, show_options.strict)
+                .map_err(|e| e.set_json_text(x.doc.json().text()))
+        });
+        for result in results {
+            let record = result?;
+            if format == OutputFormat::Jsonl {
+                writeln!(writer, "{}", nojson::Json(&record))?;
+            } else {
+                records.push(record);
+            }
         }
-        print_detail(writer, doc, show_options).map_err(|e| e.set_json_text(doc.json.text()))?;
+        writer.flush()?;
+    }
+
+    if format == OutputFormat::Json {
+        let output = nojson::json(|f| {
+            f.set_indent_size(2);
+            f.set_spacing(true);
+            f.value(&records)
+        });
+        writeln!(writer, "{output}")?;
     }
     Ok(())
 }
 
+/// An item of `--format json|jsonl` output
+struct JsonItemRecord<'a> {
+    crate_name: &'a str,
+    path: &'a crate::doc::ItemPath,
+    kind: crate::doc::ItemKind,
+    signature: String,
+    docs: Option<String>,
+    deprecation: Option<nojson::RawJsonValue<'a, 'a>>,
+    visibility: nojson::RawJsonValue<'a, 'a>,
+}
+
+impl<'a> JsonItemRecord<'a> {
+    fn new(
+        show_crate: &'a ShowCrate,
+        show_item: &'a ShowItem,
+        strict: bool,
+    ) -> crate::Result<Self> {
+        let ShowItem {
+            path, doc, item, ..
+        } = show_item;
+        Ok(Self {
+            crate_name: &show_crate.doc.crate_name,
+            path,
+            kind: item.kind,
+            signature: crate::format_item::format_signature_to_string(doc, item, strict)?,
+            docs: item.docs(doc.json())?,
+            deprecation: item.deprecation(doc.json()),
+            visibility: item.visibility(doc.json()),
+        })
+    }
+}
+
+impl nojson::DisplayJson for JsonItemRecord<'_> {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("crate", self.crate_name)?;
+            f.member("path", self.path.to_string())?;
+            f.member("kind", self.kind.as_keyword_str())?;
+            f.member("signature", &self.signature)?;
+            f.member("docs", &self.docs)?;
+            f.member("deprecation", self.deprecation)?;
+            f.member("visibility", self.visibility)
+        })
+    }
+}
+
 fn print_summary<W: std::io::Write>(
     writer: &mut W,
-    docs: &[crate::doc::CrateDoc],
-    _show_options: &ShowOptions,
+    crates: &[ShowCrate],
+    items: &[(&ShowCrate, &ShowItem)],
 ) -> crate::Result<()> {
     writeln!(writer, "# Crates Overview\n")?;
-    for doc in docs {
+    for show_crate in crates {
         writeln!(
             writer,
             "- `{}` ({} public items, {} items to show)",
-            doc.crate_name,
-            doc.public_item_count,
-            doc.show_items.len()
+            show_crate.doc.crate_name,
+            show_crate.public_item_count,
+            show_crate.items.len()
         )?;
     }
     writeln!(writer)?;
 
-    for doc in docs {
-        if doc.show_items.is_empty() {
+    if items.iter().any(|(_, x)| x.score.is_some()) {
+        // Ranked items of all crates are listed together, best first
+        writeln!(writer, "# Matching Items\n")?;
+        print_item_list(writer, items.iter().map(|(_, x)| *x))?;
+        return Ok(());
+    }
+
+    for show_crate in crates {
+        if show_crate.items.is_empty() {
             continue;
         }
+        writeln!(writer, "# Crate Items: `{}`\n", show_crate.doc.crate_name)?;
+        print_item_list(writer, show_crate.items.iter())?;
+    }
 
-        writeln!(writer, "# Crate Items: `{}`\n", doc.crate_name)?;
+    Ok(())
+}
 
-        // Calculate the longest kind keyword for padding
-        let max_kind_len = doc
-            .show_items
-            .iter()
-            .map(|(_, item)| item.kind.as_keyword_str().len())
-            .max()
-            .unwrap_or(0);
-
-        for (path, item) in &doc.show_items {
-            writeln!(
-                writer,
-                "- [{:<width$}] `{}`",
-                item.kind.as_keyword_str(),
-                path,
-                width = max_kind_len
-            )?;
-        }
+fn print_item_list<'a, W: std::io::Write>(
+    writer: &mut W,
+    items: impl Iterator<Item = &'a ShowItem<'a>> + Clone,
+) -> crate::Result<()> {
+    // Calculate the longest kind keyword for padding
+    let max_kind_len = items
+        .clone()
+        .map(|x| x.item.kind.as_keyword_str().len())
+        .max()
+        .unwrap_or(0);
 
-        writeln!(writer)?;
+    for x in items {
+        writeln!(
+            writer,
+            "- [{:<width$}] `{}`",
+            x.item.kind.as_keyword_str(),
+            x.path,
+            width = max_kind_len
+        )?;
     }
 
+    writeln!(writer)?;
     Ok(())
 }
 
+fn detail_heading(show_item: &ShowItem) -> String {
+    format!(
+        "[{}] `{}`",
+        show_item.item.kind.as_keyword_str(),
+        show_item.path
+    )
+}
+
 fn print_detail<W: std::io::Write>(
     writer: &mut W,
-    doc: &crate::doc::CrateDoc,
+    show_item: &ShowItem,
+    anchors: &std::collections::HashMap<crate::doc::ItemPath, String>,
     show_options: &ShowOptions,
 ) -> crate::Result<()> {
-    for (path, item) in &doc.show_items {
-        writeln!(writer, "# [{}] `{}`\n", item.kind.as_keyword_str(), path)?;
+    let ShowItem {
+        path,
+        doc,
+        item,
+        origin,
+        ..
+    } = show_item;
 
-        // Print inner JSON if requested
-        if show_options.show_inner_json {
-            writeln!(writer, "**Inner JSON**:\n")?;
-            writeln!(writer, "```json\n{}\n```\n", item.inner(&doc.json))?;
-        }
+    writeln!(writer, "# {}\n", detail_heading(show_item))?;
 
-        print_item_signature(writer, doc, item)?;
+    // Print inner JSON if requested
+    if show_options.show_inner_json {
+        writeln!(writer, "**Inner JSON**:\n")?;
+        writeln!(writer, "```json\n{}\n```\n", item.inner(doc.json()))?;
+    }
 
-        if let Some(deprecation_note) = item.deprecation_note(&doc.json)? {
-            if !deprecation_note.is_empty() {
-                writeln!(writer, "**Deprecated**: {}\n", deprecation_note)?;
-            } else {
-                writeln!(writer, "**Deprecated**\n")?;
-            }
-        }
+    print_item_signature(writer, doc, item, show_options)?;
+
+    if let Some(origin) = origin {
+        writeln!(writer, "**Re-exported from**: `{}`\n", origin)?;
+    }
+
+    let aliases = doc.aliases(item);
+    if !aliases.is_empty() {
+        let aliases = aliases
+            .iter()
+            .map(|path| format!("`{path}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(writer, "**Also available as**: {}\n", aliases)?;
+    }
 
-        if let Some(docs) = item.docs(&doc.json)? {
-            let formatted_docs = crate::markdown::add_rust_to_code_blocks(&docs);
-            let increased_headings = crate::markdown::increase_heading_levels(&formatted_docs);
-            writeln!(writer, "{}\n", increased_headings)?;
+    if let Some(deprecation_note) = item.deprecation_note(doc.json())? {
+        if !deprecation_note.is_empty() {
+            writeln!(writer, "**Deprecated**: {}\n", deprecation_note)?;
+        } else {
+            writeln!(writer, "**Deprecated**\n")?;
         }
+    }
 
-        let inner = item.inner(&doc.json);
-        if inner.kind().is_object() {
-            if let Some(_impls) = inner.to_member("impls")?.get() {
-                // TODO
-            }
+    if let Some(docs) = item.docs(doc.json())? {
+        // Relative HTML links are relative to the item's location in the defining crate
+        let doc_links = doc.doc_links(item)?;
+        let page_path = origin.unwrap_or(path);
+        let linked_docs = crate::markdown::rewrite_links(&docs, |dest| {
+            let target = doc_links
+                .iter()
+                .find(|(d, _)| d == dest)
+                .map(|(_, target)| target.clone())
+                .or_else(|| doc.resolve_html_link(page_path, dest))?;
+            Some(crate::markdown::LinkTarget {
+                anchor: anchors.get(&target).cloned(),
+                path: target.to_string(),
+            })
+        });
+        let formatted_docs =
+            crate::markdown::normalize_code_blocks(&linked_docs, show_options.show_hidden_lines);
+        let increased_headings = crate::markdown::increase_heading_levels(&formatted_docs);
+        writeln!(writer, "{}\n", increased_headings)?;
+    }
 
-            if let Some(_impls) = inner.to_member("implementations")?.get() {
-                // TODO
-            }
+    let inner = item.inner(doc.json());
+    if inner.kind().is_object() {
+        if let Some(impls) = inner.to_member("impls")?.get() {
+            print_implementations(writer, doc, impls, show_options)?;
         }
 
-        writeln!(writer)?;
+        if let Some(impls) = inner.to_member("implementations")?.get() {
+            print_implementors(writer, doc, impls, show_options)?;
+        }
     }
 
+    writeln!(writer)?;
+
     Ok(())
 }
 
-fn print_item_signature<W: std::io::Write>(
+fn print_implementations<W: std::io::Write>(
     writer: &mut W,
     doc: &crate::doc::CrateDoc,
-    item: &crate::doc::Item,
+    impl_ids: nojson::RawJsonValue<'_, '_>,
+    show_options: &ShowOptions,
 ) -> crate::Result<()> {
-    let inner = item.inner(&doc.json);
-
-    writeln!(writer, "```rust")?;
-    match item.kind {
-        crate::doc::ItemKind::TypeAlias | crate::doc::ItemKind::AssocType => {
-            let kw = item.kind.as_keyword_str();
-            let view = crate::item_view::TypeView::new(doc, item);
-            if let Some(ty) = view.ty()? {
-                writeln!(writer, "{kw} {} = {};", view.name()?, ty)?;
-            } else {
-                writeln!(writer, "{kw} {};", view.name()?)?;
-            }
-        }
-        crate::doc::ItemKind::TraitAlias => {
-            let kw = item.kind.as_keyword_str();
-            let name = item.name.as_ref().expect("bug");
-            let inner = item.inner(&doc.json);
-            writeln!(writer, "{kw} {} = {};", name, inner)?;
-        }
-        crate::doc::ItemKind::Primitive => {
-            let view = crate::item_view::PrimitiveView::new(doc, item);
-            writeln!(writer, "type {};", view.name())?;
-        }
-        crate::doc::ItemKind::Constant | crate::doc::ItemKind::AssocConst => {
-            let view = crate::item_view::ConstantView::new(doc, item);
-            writeln!(writer, "const {}: {};", view.name(), view.ty()?)?;
-        }
-        crate::doc::ItemKind::Module => {
-            let view = crate::item_view::ModuleView::new(doc, item);
-            let child_count = view.child_count()?;
-            writeln!(
-                writer,
-                "mod {} {{ /* {} items */ }}",
-                view.name(),
-                child_count
-            )?;
-        }
-        crate::doc::ItemKind::Macro => {
-            writeln!(writer, "{}", inner.to_unquoted_string_str()?)?;
-        }
-        crate::doc::ItemKind::ProcMacro => {
-            let view = crate::item_view::ProcMacroView::new(doc, item);
-            writeln!(writer, "{}", view.derive_attribute()?)?;
-        }
-        crate::doc::ItemKind::StructField => {
-            let view = crate::item_view::FieldView::new(doc, item);
-            writeln!(writer, "  {}: {}", view.name(), view.ty()?)?;
-        }
-        crate::doc::ItemKind::Function => {
-            let view = crate::item_view::FunctionView::new(doc, item);
-            writeln!(writer, "{}", view.signature()?)?;
-        }
-        crate::doc::ItemKind::Static => {
-            let view = crate::item_view::ConstantView::new(doc, item);
-            writeln!(writer, "static {}: {};", view.name(), view.ty()?)?;
-        }
-        crate::doc::ItemKind::Variant => {
-            let s = crate::format_item::format_enum_variant_to_string(doc, item)?;
-            writeln!(writer, "{s}")?;
-        }
-        crate::doc::ItemKind::Enum => {
-            let s = crate::format_item::format_enum_to_string(doc, item)?;
-            writeln!(writer, "{s}")?;
+    let mut inherent_impls = Vec::new();
+    let mut trait_impls = Vec::new();
+    for impl_id in impl_ids.to_array()? {
+        let Ok(impl_value) = doc.items.get(doc.json(), impl_id) else {
+            continue;
+        };
+        let impl_item = crate::doc::Item::try_from(impl_value)?;
+        let view = crate::item_view::ImplView::new(doc, &impl_item).strict(show_options.strict);
+        if view.is_generated()? {
+            continue;
         }
-        crate::doc::ItemKind::Trait => {
-            let s = crate::format_item::format_trait_to_string(doc, item)?;
-            writeln!(writer, "{s}")?;
+        if !view.is_inherent()? {
+            trait_impls.push(view.signature()?);
+        } else if view.has_public_items()? {
+            inherent_impls.push(view.signature()?);
         }
-        crate::doc::ItemKind::Struct => {
-            let s = crate::format_item::format_struct_to_string(doc, item)?;
-            writeln!(writer, "{s}")?;
+    }
+
+    if inherent_impls.is_empty() && trait_impls.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "## Implementations\n")?;
+    writeln!(writer, "```rust")?;
+    for (i, signature) in inherent_impls.iter().chain(&trait_impls).enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
         }
-        crate::doc::ItemKind::Union => {
-            let s = crate::format_item::format_union_to_string(doc, item)?;
-            writeln!(writer, "{s}")?;
+        writeln!(
+            writer,
+            "{}",
+            crate::layout::wrap_lines(signature, show_options.width)
+        )?;
+    }
+    writeln!(writer, "```\n")?;
+
+    Ok(())
+}
+
+fn print_implementors<W: std::io::Write>(
+    writer: &mut W,
+    doc: &crate::doc::CrateDoc,
+    impl_ids: nojson::RawJsonValue<'_, '_>,
+    show_options: &ShowOptions,
+) -> crate::Result<()> {
+    let mut headers = Vec::new();
+    for impl_id in impl_ids.to_array()? {
+        let Ok(impl_value) = doc.items.get(doc.json(), impl_id) else {
+            continue;
+        };
+        let impl_item = crate::doc::Item::try_from(impl_value)?;
+        let view = crate::item_view::ImplView::new(doc, &impl_item).strict(show_options.strict);
+        if view.is_generated()? {
+            continue;
         }
-        kind => todo!("{kind:?}: {inner}"),
+        headers.push(view.header()?);
+    }
+
+    if headers.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "## Implementors\n")?;
+    writeln!(writer, "```rust")?;
+    for header in headers {
+        writeln!(
+            writer,
+            "{}",
+            crate::layout::wrap_lines(&header, show_options.width)
+        )?;
     }
     writeln!(writer, "```\n")?;
 
     Ok(())
 }
+
+fn print_item_signature<W: std::io::Write>(
+    writer: &mut W,
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+    show_options: &ShowOptions,
+) -> crate::Result<()> {
+    let signature = crate::format_item::format_signature_to_string(doc, item, show_options.strict)?;
+    writeln!(writer, "```rust")?;
+    writeln!(
+        writer,
+        "{}",
+        crate::layout::wrap_lines(&signature, show_options.width)
+    )?;
+    writeln!(writer, "```\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_item_records() -> crate::Result<()> {
+        let doc = crate::doc::CrateDoc::parse(
+            std::path::PathBuf::from(""),
+            r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "f", "visibility": "public", "inner": {"function": FN}, "docs": "Adds one.", "deprecation": {"since": "1.0", "note": "use g"}},
+            "2": {"id": 2, "name": "g", "visibility": {"restricted": {"parent": 0, "path": "crate"}}, "inner": {"function": FN}, "docs": null, "deprecation": null}
+        }}"#
+            .replace(
+                "FN",
+                r#"{"sig": {"inputs": [["x", {"primitive": "u32"}]], "output": {"primitive": "u32"}, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}"#,
+            )
+            .as_str(),
+        )?;
+        let docs = std::slice::from_ref(&doc);
+        let show_crate = ShowCrate::new(&doc, docs);
+
+        // Items with restricted visibility are not collected, so `g` is shown explicitly
+        let g = crate::doc::Item::try_from(
+            doc.items
+                .get_by_id(doc.json(), crate::doc::ItemId(2))
+                .expect("bug"),
+        )?;
+        let g_path = crate::doc::ItemPath::from_segments(vec!["t".to_owned(), "g".to_owned()]);
+        let show_g = ShowItem {
+            path: g_path,
+            doc: &doc,
+            item: &g,
+            origin: None,
+            score: None,
+        };
+
+        let records: Vec<_> = show_crate
+            .items
+            .iter()
+            .chain(std::iter::once(&show_g))
+            .map(|x| {
+                JsonItemRecord::new(&show_crate, x, false).map(|r| nojson::Json(r).to_string())
+            })
+            .collect::<crate::Result<_>>()?;
+        assert_eq!(
+            records,
+            [
+                r#"{"crate":"t","path":"t","kind":"mod","signature":"mod t { /* 2 items */ }","docs":null,"deprecation":null,"visibility":"public"}"#,
+                r#"{"crate":"t","path":"t::f","kind":"fn","signature":"fn f(x: u32) -> u32","docs":"Adds one.","deprecation":{"since":"1.0","note":"use g"},"visibility":"public"}"#,
+                r#"{"crate":"t","path":"t::g","kind":"fn","signature":"fn g(x: u32) -> u32","docs":null,"deprecation":null,"visibility":{"restricted":{"parent":0,"path":"crate"}}}"#,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn print_inherent_impls_before_trait_impls() -> crate::Result<()> {
+        let impl_item = |id: u32, trait_info: &str, bound: &str, member_id: u32| {
+            format!(
+                r#""{id}": {{"id": {id}, "name": null, "visibility": "default", "docs": null, "deprecation": null, "inner": {{"impl": {{"is_unsafe": false, "generics": {{"params": [{{"name": "T", "kind": {{"type": {{"bounds": [], "default": null, "is_synthetic": false}}}}}}], "where_predicates": [{{"bound_predicate": {{"type": {{"generic": "T"}}, "bounds": [{{"trait_bound": {{"trait": {{"path": "{bound}", "id": 91, "args": null}}, "generic_params": [], "modifier": "none"}}}}], "generic_params": []}}}}]}}, "provided_trait_methods": [], "trait": {trait_info}, "for": {{"resolved_path": {{"path": "S", "id": 1, "args": {{"angle_bracketed": {{"args": [{{"type": {{"generic": "T"}}}}], "constraints": []}}}}}}}}, "items": [{member_id}], "is_negative": false, "is_synthetic": false, "blanket_impl": null}}}}}}"#
+            )
+        };
+        let function = |id: u32, name: &str, visibility: &str, inputs: &str| {
+            format!(
+                r#""{id}": {{"id": {id}, "name": "{name}", "visibility": "{visibility}", "docs": null, "deprecation": null, "inner": {{"function": {{"sig": {{"inputs": [{inputs}], "output": {{"generic": "Self"}}, "is_c_variadic": false}}, "generics": {{"params": [], "where_predicates": []}}, "header": {{"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}}, "has_body": true}}}}}}"#
+            )
+        };
+        // The trait impl (10) is listed before the inherent impl (11)
+        let text = format!(
+            r#"{{"format_version": 57, "root": 0, "index": {{
+            "0": {{"id": 0, "name": "t", "visibility": "public", "inner": {{"module": {{"items": [1]}}}}, "docs": null, "deprecation": null}},
+            "1": {{"id": 1, "name": "S", "visibility": "public", "inner": {{"struct": {{"kind": "unit", "impls": [10, 11]}}}}, "docs": null, "deprecation": null}},
+            {},
+            {},
+            {},
+            {}
+        }}}}"#,
+            impl_item(
+                10,
+                r#"{"path": "Clone", "id": 90, "args": null}"#,
+                "Clone",
+                20
+            ),
+            impl_item(11, "null", "Default", 21),
+            function(
+                20,
+                "clone",
+                "default",
+                r#"["self", {"borrowed_ref": {"lifetime": null, "is_mutable": false, "type": {"generic": "Self"}}}]"#
+            ),
+            function(21, "new", "public", ""),
+        );
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), &text)?;
+        let s = crate::doc::Item::try_from(
+            doc.items
+                .get_by_id(doc.json(), crate::doc::ItemId(1))
+                .expect("bug"),
+        )?;
+        let impls = s.inner(doc.json()).to_member("impls")?.required()?;
+        let show_options = ShowOptions {
+            show_inner_json: false,
+            show_hidden_lines: false,
+            width: 100,
+            verbose: false,
+            format: OutputFormat::Markdown,
+            strict: true,
+        };
+
+        let mut output = Vec::new();
+        print_implementations(&mut output, &doc, impls, &show_options)?;
+        assert_eq!(
+            String::from_utf8(output).expect("bug"),
+            concat!(
+                "## Implementations\n\n",
+                "```rust\n",
+                "impl<T> S<T>\n",
+                "where\n",
+                "    T: Default,\n",
+                "{\n",
+                "    fn new() -> Self;\n",
+                "}\n",
+                "\n",
+                "impl<T> Clone for S<T>\n",
+                "where\n",
+                "    T: Clone,\n",
+                "{\n",
+                "    fn clone(&self) -> Self;\n",
+                "}\n",
+                "```\n\n",
+            )
+        );
+        Ok(())
+    }
+}
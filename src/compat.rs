@@ -0,0 +1,286 @@
+/// Oldest rustdoc JSON `format_version` that siru can read (with the adapters in this module)
+pub const MIN_FORMAT_VERSION: u32 = 33;
+
+/// Newest rustdoc JSON `format_version` that siru has been tested with
+///
+/// JSON of this version is used as-is; older JSON is upgraded to this schema by [`upgrade`].
+pub const MAX_FORMAT_VERSION: u32 = 57;
+
+/// Reads and validates the `format_version` of a rustdoc JSON file
+///
+/// Versions newer than [`MAX_FORMAT_VERSION`] are accepted because the schema often stays
+/// compatible; see [`newer_version_error`] for reporting failures with them.
+pub fn check_format_version(
+    root: nojson::RawJsonValue<'_, '_>,
+) -> Result<u32, nojson::JsonParseError> {
+    let Some(version) = root.to_member("format_version")?.get() else {
+        return Err(root.invalid(
+            "missing `format_version` member (is this a rustdoc JSON file built with `--output-format json`?)",
+        ));
+    };
+    let format_version: u32 = version.try_into()?;
+    if format_version < MIN_FORMAT_VERSION {
+        return Err(version.invalid(format!(
+            "unsupported rustdoc JSON format version {format_version} (supported versions: {}); please rebuild the documentation with a newer toolchain",
+            supported_versions()
+        )));
+    }
+    Ok(format_version)
+}
+
+/// Wraps an error that occurred while reading JSON newer than [`MAX_FORMAT_VERSION`]
+pub fn newer_version_error(
+    root: nojson::RawJsonValue<'_, '_>,
+    error: nojson::JsonParseError,
+) -> nojson::JsonParseError {
+    let Ok(Some(version)) = root.to_member("format_version").map(|x| x.get()) else {
+        return error;
+    };
+    version.invalid(format!(
+        "rustdoc JSON format version {} is newer than the supported versions ({}), and its schema could not be read: {error}",
+        version.as_raw_str(),
+        supported_versions()
+    ))
+}
+
+fn supported_versions() -> String {
+    format!("{MIN_FORMAT_VERSION} to {MAX_FORMAT_VERSION}")
+}
+
+/// Returns whether JSON of the given version needs to be passed through [`upgrade`]
+pub fn needs_upgrade(format_version: u32) -> bool {
+    format_version < MAX_FORMAT_VERSION
+}
+
+/// Rewrites rustdoc JSON of an older format version to the schema of [`MAX_FORMAT_VERSION`]
+///
+/// The known differences are detected by their shape rather than by version number:
+///
+/// - item ids given as strings such as `"0:1:2"` (now integers; before version 35),
+/// - item kinds that have since been renamed (e.g., `typedef` and `import`),
+/// - `glob` of `use` items (now `is_glob`),
+/// - `is_object_safe` of traits (now `is_dyn_compatible`), and
+/// - `attrs` given as source strings such as `"#[non_exhaustive]"`
+///   (now `"non_exhaustive"` or `{"other": "#[...]"}`).
+pub fn upgrade(root: nojson::RawJsonValue<'_, '_>) -> String {
+    let ids = std::cell::RefCell::new(std::collections::HashMap::new());
+    nojson::Json(Upgrade {
+        value: root,
+        key: None,
+        ids: &ids,
+    })
+    .to_string()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Upgrade<'a, 'text, 'raw> {
+    value: nojson::RawJsonValue<'text, 'raw>,
+    /// Name of the (original) member containing `value`
+    key: Option<&'a str>,
+    /// Integer ids assigned to string ids
+    ids: &'a std::cell::RefCell<std::collections::HashMap<String, u32>>,
+}
+
+impl Upgrade<'_, '_, '_> {
+    /// Returns the integer id for a string id, assigning a new one on first use
+    fn upgrade_id(&self, id: &str) -> u32 {
+        let mut ids = self.ids.borrow_mut();
+        let next_id = ids.len() as u32;
+        *ids.entry(id.to_owned()).or_insert(next_id)
+    }
+}
+
+impl nojson::DisplayJson for Upgrade<'_, '_, '_> {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        let value = self.value;
+        if value.kind().is_object() {
+            let members = value.to_object().map_err(|_| std::fmt::Error)?;
+            // The values of `links` are ids, and the keys of `index` and `paths` are ids
+            let has_id_keys = matches!(self.key, Some("index" | "paths"));
+            f.object(|f| {
+                for (name, member) in members {
+                    let name = name.to_unquoted_string_str().map_err(|_| std::fmt::Error)?;
+                    let child = Upgrade {
+                        value: member,
+                        key: Some(if self.key == Some("links") {
+                            "links"
+                        } else {
+                            &name
+                        }),
+                        ids: self.ids,
+                    };
+                    // Keys of integer ids (version 35 and later) are digits and kept as they are
+                    if has_id_keys && !name.bytes().all(|b| b.is_ascii_digit()) {
+                        f.member(self.upgrade_id(&name), child)?;
+                    } else {
+                        f.member(upgrade_member_name(self.key, &name), child)?;
+                    }
+                }
+                Ok(())
+            })
+        } else if value.kind().is_array() {
+            let elements = value.to_array().map_err(|_| std::fmt::Error)?;
+            if self.key == Some("attrs") {
+                f.array(|f| {
+                    for element in elements {
+                        match upgrade_attr(element) {
+                            Some(attr) => f.element(attr)?,
+                            None => f.element(element)?,
+                        }
+                    }
+                    Ok(())
+                })
+            } else {
+                f.array(|f| {
+                    f.elements(elements.map(|value| Upgrade {
+                        value,
+                        key: self.key,
+                        ids: self.ids,
+                    }))
+                })
+            }
+        } else if value.kind().is_string() && self.key.is_some_and(is_id_member_name) {
+            let id = value
+                .to_unquoted_string_str()
+                .map_err(|_| std::fmt::Error)?;
+            f.value(self.upgrade_id(&id))
+        } else {
+            f.value(value)
+        }
+    }
+}
+
+/// Returns whether members of the given name hold ids (or arrays of ids)
+fn is_id_member_name(name: &str) -> bool {
+    matches!(
+        name,
+        "root"
+            | "id"
+            | "items"
+            | "impls"
+            | "implementations"
+            | "fields"
+            | "variants"
+            | "tuple"
+            | "links"
+    )
+}
+
+fn upgrade_member_name<'a>(parent_key: Option<&str>, name: &'a str) -> &'a str {
+    match (parent_key, name) {
+        (Some("inner"), "typedef") => "type_alias",
+        (Some("inner"), "import") => "use",
+        (Some("inner"), "foreign_type") => "extern_type",
+        (Some("import" | "use"), "glob") => "is_glob",
+        (Some("trait"), "is_object_safe") => "is_dyn_compatible",
+        _ => name,
+    }
+}
+
+/// Converts an attribute given as a source string into its structured form
+fn upgrade_attr(attr: nojson::RawJsonValue<'_, '_>) -> Option<AttrJson> {
+    if !attr.kind().is_string() {
+        return None;
+    }
+    let text = attr.to_unquoted_string_str().ok()?;
+    if !text.starts_with("#[") {
+        // Already a unit attribute such as `"non_exhaustive"`
+        return None;
+    }
+    Some(match text.as_ref() {
+        "#[non_exhaustive]" => AttrJson::Unit("non_exhaustive"),
+        "#[automatically_derived]" => AttrJson::Unit("automatically_derived"),
+        "#[no_mangle]" => AttrJson::Unit("no_mangle"),
+        _ => AttrJson::Other(text.into_owned()),
+    })
+}
+
+#[derive(Debug)]
+enum AttrJson {
+    Unit(&'static str),
+    Other(String),
+}
+
+impl nojson::DisplayJson for AttrJson {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        match self {
+            AttrJson::Unit(name) => f.value(name),
+            AttrJson::Other(text) => f.object(|f| f.member("other", text)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_format_versions() -> crate::Result<()> {
+        let json = nojson::RawJson::parse(r#"{"format_version": 57}"#)?;
+        assert_eq!(check_format_version(json.value())?, 57);
+
+        let json = nojson::RawJson::parse(r#"{"format_version": 12}"#)?;
+        let error = check_format_version(json.value()).expect_err("too old");
+        assert!(error.to_string().contains("supported versions: 33 to 57"));
+
+        let json = nojson::RawJson::parse(r#"{"root": 0}"#)?;
+        assert!(check_format_version(json.value()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_older_schema() -> crate::Result<()> {
+        let json = nojson::RawJson::parse(
+            r##"{"format_version": 33, "index": {
+                "1": {"inner": {"typedef": {"type": {"generic": "T"}}}, "attrs": ["#[non_exhaustive]", "#[track_caller]", "automatically_derived"]},
+                "2": {"inner": {"import": {"source": "a::b", "glob": true}}, "attrs": []},
+                "3": {"inner": {"trait": {"is_object_safe": true, "items": []}}, "docs": "#[non_exhaustive]"}
+            }}"##,
+        )?;
+        assert_eq!(
+            upgrade(json.value()),
+            concat!(
+                r#"{"format_version":33,"index":{"#,
+                r##""1":{"inner":{"type_alias":{"type":{"generic":"T"}}},"attrs":["non_exhaustive",{"other":"#[track_caller]"},"automatically_derived"]},"##,
+                r#""2":{"inner":{"use":{"source":"a::b","is_glob":true}},"attrs":[]},"#,
+                r##""3":{"inner":{"trait":{"is_dyn_compatible":true,"items":[]}},"docs":"#[non_exhaustive]"}"##,
+                r#"}}"#
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_string_ids() -> crate::Result<()> {
+        let text = r#"{"format_version": 33, "root": "0:0:0", "index": {
+            "0:0:0": {"id": "0:0:0", "crate_id": 0, "name": "t", "visibility": "public", "docs": null, "links": {}, "attrs": [], "deprecation": null,
+                      "inner": {"module": {"is_crate": true, "items": ["0:1:2", "0:3:4"], "is_stripped": false}}},
+            "0:1:2": {"id": "0:1:2", "crate_id": 0, "name": "S", "visibility": "public", "docs": "See [`f`].", "links": {"`f`": "0:3:4"}, "attrs": [], "deprecation": null,
+                      "inner": {"struct": {"kind": {"tuple": ["0:2:3", null]}, "generics": {"params": [], "where_predicates": []}, "impls": []}}},
+            "0:2:3": {"id": "0:2:3", "crate_id": 0, "name": "0", "visibility": "public", "docs": null, "links": {}, "attrs": [], "deprecation": null,
+                      "inner": {"struct_field": {"primitive": "u8"}}},
+            "0:3:4": {"id": "0:3:4", "crate_id": 0, "name": "f", "visibility": "public", "docs": null, "links": {}, "attrs": [], "deprecation": null,
+                      "inner": {"function": {"sig": {"inputs": [["x", {"resolved_path": {"path": "S", "id": "0:1:2", "args": null}}]], "output": null, "is_c_variadic": false},
+                                             "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}}
+        }, "paths": {"0:1:2": {"crate_id": 0, "path": ["t", "S"], "kind": "struct"}}}"#;
+        let json = nojson::RawJson::parse(text)?;
+        let upgraded = upgrade(json.value());
+        assert!(upgraded.contains(r#""root":0,"index":{"0":{"id":0,"#));
+        assert!(upgraded.contains(r#""kind":{"tuple":[3,null]}"#));
+        assert!(upgraded.contains(r#""links":{"`f`":2}"#));
+        assert!(upgraded.contains(r#""paths":{"1":{"#));
+
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+        let paths: Vec<_> = doc
+            .show_items
+            .iter()
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(paths, ["t", "t::S", "t::f"]);
+        assert_eq!(
+            crate::format_item::format_signature_to_string(&doc, &doc.show_items[2].1, false)?,
+            "fn f(x: S)"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub total: usize,
+    pub documented: usize,
+    /// Documented items whose docs contain fenced code blocks
+    pub with_examples: usize,
+}
+
+impl Counts {
+    pub fn percentage(self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.total += other.total;
+        self.documented += other.documented;
+        self.with_examples += other.with_examples;
+    }
+}
+
+impl nojson::DisplayJson for Counts {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("total", self.total)?;
+            f.member("documented", self.documented)?;
+            f.member("with_examples", self.with_examples)?;
+            f.member("percentage", self.percentage())
+        })
+    }
+}
+
+/// Per-kind counts (in the order kinds first appear)
+#[derive(Debug, Default, Clone)]
+pub struct KindCounts(pub Vec<(crate::doc::ItemKind, Counts)>);
+
+impl KindCounts {
+    pub fn total(&self) -> Counts {
+        let mut total = Counts::default();
+        for (_, counts) in &self.0 {
+            total.add(*counts);
+        }
+        total
+    }
+
+    fn add(&mut self, kind: crate::doc::ItemKind, counts: Counts) {
+        if let Some((_, c)) = self.0.iter_mut().find(|(k, _)| *k == kind) {
+            c.add(counts);
+        } else {
+            self.0.push((kind, counts));
+        }
+    }
+}
+
+impl nojson::DisplayJson for KindCounts {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            for (kind, counts) in &self.0 {
+                f.member(kind.as_str(), counts)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Documentation coverage of the public items of a crate
+#[derive(Debug)]
+pub struct CrateCoverage {
+    pub crate_name: String,
+    pub by_kind: KindCounts,
+    /// Counts of the items whose nearest enclosing module is the given one
+    pub by_module: Vec<(crate::doc::ItemPath, KindCounts)>,
+    pub undocumented: Vec<(crate::doc::ItemPath, crate::doc::ItemKind)>,
+}
+
+impl CrateCoverage {
+    pub fn new(doc: &crate::doc::CrateDoc) -> crate::Result<Self> {
+        let module_paths: std::collections::HashSet<_> = doc
+            .show_items
+            .iter()
+            .filter(|(_, item)| item.kind == crate::doc::ItemKind::Module)
+            .map(|(path, _)| path)
+            .collect();
+
+        let mut this = Self {
+            crate_name: doc.crate_name.clone(),
+            by_kind: KindCounts::default(),
+            by_module: Vec::new(),
+            undocumented: Vec::new(),
+        };
+        for (path, item) in &doc.show_items {
+            if !item.kind.is_shown_by_default() {
+                continue;
+            }
+
+            let docs = item.docs(doc.json())?.unwrap_or_default();
+            let is_documented = !docs.trim().is_empty();
+            let counts = Counts {
+                total: 1,
+                documented: usize::from(is_documented),
+                with_examples: usize::from(is_documented && has_code_block(&docs)),
+            };
+            if !is_documented {
+                this.undocumented.push((path.clone(), item.kind));
+            }
+            this.by_kind.add(item.kind, counts);
+
+            let module_path = enclosing_module(path, &module_paths);
+            match this.by_module.iter_mut().find(|(p, _)| *p == module_path) {
+                Some((_, module_counts)) => module_counts.add(item.kind, counts),
+                None => {
+                    let mut module_counts = KindCounts::default();
+                    module_counts.add(item.kind, counts);
+                    this.by_module.push((module_path, module_counts));
+                }
+            }
+        }
+        Ok(this)
+    }
+}
+
+/// Returns the nearest module containing `path` (the crate root module contains itself)
+fn enclosing_module(
+    path: &crate::doc::ItemPath,
+    module_paths: &std::collections::HashSet<&crate::doc::ItemPath>,
+) -> crate::doc::ItemPath {
+    let mut module_path = path.parent().unwrap_or_else(|| path.clone());
+    while !module_paths.contains(&module_path)
+        && let Some(parent) = module_path.parent()
+    {
+        module_path = parent;
+    }
+    module_path
+}
+
+fn has_code_block(docs: &str) -> bool {
+    docs.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("```") || line.starts_with("~~~")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_documented_items_per_module() -> crate::Result<()> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2]}}, "docs": "Root docs", "deprecation": null},
+            "1": {"id": 1, "name": "A", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": "With an example\n\n```\nlet a = t::A;\n```", "deprecation": null},
+            "2": {"id": 2, "name": "sub", "visibility": "public", "inner": {"module": {"items": [3, 4]}}, "docs": "  ", "deprecation": null},
+            "3": {"id": 3, "name": "B", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": "C", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": "Docs", "deprecation": null}
+        }}"#;
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+        let coverage = CrateCoverage::new(&doc)?;
+
+        assert_eq!(
+            coverage.by_kind.total(),
+            Counts {
+                total: 5,
+                documented: 3,
+                with_examples: 1
+            }
+        );
+        let modules: Vec<_> = coverage
+            .by_module
+            .iter()
+            .map(|(path, counts)| {
+                let total = counts.total();
+                format!("{path}: {}/{}", total.documented, total.total)
+            })
+            .collect();
+        assert_eq!(modules, ["t: 2/3", "t::sub: 1/2"]);
+        let undocumented: Vec<_> = coverage
+            .undocumented
+            .iter()
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(undocumented, ["t::sub", "t::sub::B"]);
+        Ok(())
+    }
+}
@@ -62,7 +62,7 @@ impl std::fmt::Display for ItemKind {
 
 impl ItemKind {
     pub const KEYWORDS: &'static str =
-        "mod|enum|union|variant|struct|field|type|fn|const|static|trait|macro";
+        "mod|enum|union|variant|struct|field|type|fn|const|static|trait|macro|impl|use";
     pub const MAIN_KEYWORDS: &'static str = "mod|enum|struct|trait|fn|...";
 
     pub fn parse_keyword_str(s: &str) -> Option<Vec<Self>> {
@@ -83,13 +83,17 @@ impl ItemKind {
             "static" => Some(vec![ItemKind::Static]),
             "trait" => Some(vec![ItemKind::Trait, ItemKind::TraitAlias]),
             "macro" => Some(vec![ItemKind::Macro, ItemKind::ProcMacro]),
-            // NOTE: Filters out unnamed items
-            // "use" => Some(vec![ItemKind::Use]),
-            // "impl" => Some(vec![ItemKind::Impl]),
+            "use" => Some(vec![ItemKind::Use]),
+            "impl" => Some(vec![ItemKind::Impl]),
             _ => None,
         }
     }
 
+    /// Returns `false` for unnamed items (`use` and `impl`) that are only shown when explicitly requested
+    pub fn is_shown_by_default(self) -> bool {
+        !matches!(self, ItemKind::Use | ItemKind::Impl)
+    }
+
     /// Returns the Rust keyword representation for this item kind (as it appears in source code)
     pub fn as_keyword_str(self) -> &'static str {
         match self {
@@ -171,9 +175,11 @@ impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for ItemKind {
 
 #[derive(Debug, Clone)]
 pub struct Item {
+    pub id: ItemId,
     pub name: Option<String>,
     pub kind: ItemKind,
     pub is_public: bool,
+    pub visibility_index: JsonValueIndex,
     pub docs_index: Option<JsonValueIndex>,
     pub deprecation_index: Option<JsonValueIndex>,
     pub inner_index: JsonValueIndex,
@@ -183,6 +189,7 @@ impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for Item {
     type Error = nojson::JsonParseError;
 
     fn try_from(value: nojson::RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        let id = value.to_member("id")?.required()?.try_into()?;
         let name = value.to_member("name")?.required()?.try_into()?;
         let (kind, inner) = value
             .to_member("inner")?
@@ -190,21 +197,23 @@ impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for Item {
             .to_object()?
             .next()
             .ok_or_else(|| value.invalid("empty inner"))?;
-        let kind = kind.try_into().inspect_err(|_| println!("{value}"))?;
+        let kind = kind.try_into()?;
         let inner_index = inner.try_into()?;
-        let visibility = value
-            .to_member("visibility")?
-            .required()?
-            .to_unquoted_string_str()?;
+        let visibility = value.to_member("visibility")?.required()?;
+        let visibility_index = visibility.try_into()?;
         let docs_index = value.to_member("docs")?.required()?.try_into()?;
         let deprecation_index = value.to_member("deprecation")?.required()?.try_into()?;
 
-        let is_public =
-            visibility.as_ref() == "public" || matches!(kind, ItemKind::Impl | ItemKind::Variant);
+        // `restricted` visibilities are objects rather than strings
+        let is_public = (visibility.kind().is_string()
+            && visibility.to_unquoted_string_str()? == "public")
+            || matches!(kind, ItemKind::Impl | ItemKind::Variant);
         Ok(Self {
+            id,
             name,
             kind,
             is_public,
+            visibility_index,
             docs_index,
             deprecation_index,
             inner_index,
@@ -218,6 +227,19 @@ impl Item {
             .expect("bug")
     }
 
+    pub fn visibility<'a>(&self, json: &'a nojson::RawJsonOwned) -> nojson::RawJsonValue<'a, 'a> {
+        json.get_value_by_index(self.visibility_index.get())
+            .expect("bug")
+    }
+
+    pub fn deprecation<'a>(
+        &self,
+        json: &'a nojson::RawJsonOwned,
+    ) -> Option<nojson::RawJsonValue<'a, 'a>> {
+        self.deprecation_index
+            .map(|index| json.get_value_by_index(index.get()).expect("bug"))
+    }
+
     pub fn docs(
         &self,
         json: &nojson::RawJsonOwned,
@@ -242,10 +264,32 @@ impl Item {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ItemPath(Vec<String>);
 
 impl ItemPath {
+    pub fn from_segments(segments: Vec<String>) -> Self {
+        Self(segments)
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    fn join(&self, segment: impl Into<String>) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(segment.into());
+        Self(segments)
+    }
+
+    /// Replaces the `prefix` of this path with `new_prefix`, if this path starts with `prefix`
+    pub fn rebase(&self, prefix: &ItemPath, new_prefix: &ItemPath) -> Option<Self> {
+        let rest = self.0.strip_prefix(prefix.0.as_slice())?;
+        let mut segments = new_prefix.0.clone();
+        segments.extend_from_slice(rest);
+        Some(Self(segments))
+    }
+
     pub fn crate_name(&self) -> &str {
         &self.0[0]
     }
@@ -253,6 +297,10 @@ impl ItemPath {
     pub fn name(&self) -> &str {
         self.0.last().expect("bug")
     }
+
+    pub fn parent(&self) -> Option<Self> {
+        (self.0.len() > 1).then(|| Self(self.0[..self.0.len() - 1].to_vec()))
+    }
 }
 
 impl std::fmt::Display for ItemPath {
@@ -267,10 +315,30 @@ impl std::fmt::Display for ItemPath {
     }
 }
 
+/// An item re-exported from another crate (e.g., `pub use other_crate::Thing;`)
+#[derive(Debug, Clone)]
+pub struct ExternalReexport {
+    /// Path of the re-exported item in this crate (the importing module for glob imports)
+    pub path: ItemPath,
+    pub crate_name: String,
+    /// Path of the item in the crate that defines it
+    pub target_path: ItemPath,
+    pub is_glob: bool,
+}
+
 #[derive(Debug)]
 pub struct CrateItems(std::collections::HashMap<ItemId, JsonValueIndex>);
 
 impl CrateItems {
+    pub fn get_by_id<'a>(
+        &self,
+        json: &'a nojson::RawJsonOwned,
+        id: ItemId,
+    ) -> Option<nojson::RawJsonValue<'a, 'a>> {
+        let i = self.0.get(&id)?;
+        Some(json.get_value_by_index(i.get()).expect("bug"))
+    }
+
     pub fn get<'a>(
         &self,
         json: &'a nojson::RawJsonOwned,
@@ -296,17 +364,73 @@ impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for CrateItems {
 #[derive(Debug)]
 pub struct CrateDoc {
     pub path: std::path::PathBuf,
-    pub json: nojson::RawJsonOwned,
+    /// `format_version` of the file (the JSON is upgraded to the current schema if it is older)
+    pub format_version: u32,
+    /// Loaded on demand for docs restored from [`crate::cache`] (see [`CrateDoc::load_json`])
+    json: std::sync::OnceLock<nojson::RawJsonOwned>,
     pub crate_name: String,
     pub items: CrateItems,
     pub root_module_index: JsonValueIndex,
     pub show_items: Vec<(ItemPath, Item)>,
+    /// Indices of `show_items` by item id (of the canonical path of each item)
+    show_item_indices: std::collections::HashMap<ItemId, usize>,
     pub public_item_count: usize,
+    pub aliases: std::collections::HashMap<ItemId, Vec<ItemPath>>,
+    pub external_reexports: Vec<ExternalReexport>,
+    /// Signatures of the public functions, if restored from [`crate::cache`] (otherwise they
+    /// are read from the JSON)
+    pub function_sigs: Option<std::collections::HashMap<ItemId, crate::search::FunctionSig>>,
 }
 
 impl CrateDoc {
     pub fn parse(path: std::path::PathBuf, text: &str) -> Result<Self, nojson::JsonParseError> {
-        let json = nojson::RawJsonOwned::parse(text)?;
+        let (json, format_version) = parse_json(text)?;
+        Self::from_json(path, json, format_version).map_err(|e| {
+            if format_version > crate::compat::MAX_FORMAT_VERSION
+                && let Ok(json) = nojson::RawJson::parse(text)
+            {
+                crate::compat::newer_version_error(json.value(), e)
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Finds the crate name in rustdoc JSON text without parsing all of it
+    ///
+    /// Returns `None` if the name cannot be found this way (e.g., for malformed JSON), in which
+    /// case the text has to be parsed with [`CrateDoc::parse`] to know the name.
+    pub fn peek_crate_name(text: &str) -> Option<String> {
+        let mut scanner = crate::json::JsonScanner::new(text);
+        scanner.enter_object()?;
+        let mut root_id = None;
+        while let Some(key) = scanner.next_key() {
+            match (key, root_id) {
+                ("root", _) => root_id = Some(scanner.take_value()?.trim_matches('"')),
+                ("index", Some(root_id)) => {
+                    scanner.enter_object()?;
+                    while let Some(item_id) = scanner.next_key() {
+                        let item = scanner.take_value()?;
+                        if item_id == root_id {
+                            let item = nojson::RawJson::parse(item).ok()?;
+                            return item.value().to_member("name").ok()?.get()?.try_into().ok();
+                        }
+                    }
+                    return None;
+                }
+                _ => {
+                    scanner.take_value()?;
+                }
+            }
+        }
+        None
+    }
+
+    fn from_json(
+        path: std::path::PathBuf,
+        json: nojson::RawJsonOwned,
+        format_version: u32,
+    ) -> Result<Self, nojson::JsonParseError> {
         let value = json.value();
         let root_module_id_value = value.to_member("root")?.required()?;
         let items: CrateItems = value.to_member("index")?.required()?.try_into()?;
@@ -316,31 +440,393 @@ impl CrateDoc {
             .required()?
             .try_into()?;
         let root_module_index = root_module_value.try_into()?;
-        let mut this = Self {
+        let mut collector = PublicItemCollector::new(&json, &items);
+        collector.collect(root_module_value)?;
+        let preferred_paths = collector.preferred_paths()?;
+        if !preferred_paths.is_empty() {
+            // Collect again so that items (and their members) get the paths rustdoc prefers
+            let mut retry = PublicItemCollector::new(&json, &items);
+            retry.preferred_paths = preferred_paths;
+            retry.collect(root_module_value)?;
+            if retry
+                .preferred_paths
+                .keys()
+                .all(|id| retry.visited.contains(id))
+            {
+                collector = retry;
+            }
+        }
+        let PublicItemCollector {
+            public_items: show_items,
+            aliases,
+            external_reexports,
+            ..
+        } = collector;
+        let public_item_count = show_items
+            .iter()
+            .filter(|(_, item)| item.kind.is_shown_by_default())
+            .count();
+        Ok(Self {
             path,
-            json,
+            json: std::sync::OnceLock::from(json),
+            format_version,
             crate_name,
             items,
             root_module_index,
-            show_items: Vec::new(),
-            public_item_count: 0,
+            show_item_indices: show_item_indices(&show_items),
+            show_items,
+            public_item_count,
+            aliases,
+            external_reexports,
+            function_sigs: None,
+        })
+    }
+
+    /// Returns the rustdoc JSON of this crate
+    ///
+    /// # Panics
+    ///
+    /// Panics if the doc was restored from [`crate::cache`] and [`CrateDoc::load_json`]
+    /// has not been called yet.
+    pub fn json(&self) -> &nojson::RawJsonOwned {
+        self.json.get().expect("bug: rustdoc JSON is not loaded")
+    }
+
+    /// Parses `text` (the content of [`CrateDoc::path`]) unless the JSON is already loaded
+    pub fn load_json(&self, text: &str) -> Result<(), nojson::JsonParseError> {
+        if self.json.get().is_none() {
+            let (json, _) = parse_json(text)?;
+            let _ = self.json.set(json);
+        }
+        Ok(())
+    }
+
+    /// Returns whether the JSON is loaded (it is not for docs restored from [`crate::cache`])
+    pub fn is_json_loaded(&self) -> bool {
+        self.json.get().is_some()
+    }
+
+    /// Returns the data computed from the JSON when the doc was parsed, for [`crate::cache`]
+    pub fn index(&self) -> impl nojson::DisplayJson + '_ {
+        nojson::object(|f| {
+            f.member("crate_name", &self.crate_name)?;
+            f.member("format_version", self.format_version)?;
+            f.member("root_module_index", self.root_module_index.get())?;
+            f.member(
+                "items",
+                nojson::array(|f| {
+                    for (id, index) in &self.items.0 {
+                        f.element([id.0, index.get()])?;
+                    }
+                    Ok(())
+                }),
+            )?;
+            f.member(
+                "show_items",
+                nojson::array(|f| {
+                    for (path, item) in &self.show_items {
+                        f.element(nojson::array(|f| {
+                            f.element(&path.0)?;
+                            f.element(item_index(item))
+                        }))?;
+                    }
+                    Ok(())
+                }),
+            )?;
+            f.member("public_item_count", self.public_item_count)?;
+            f.member(
+                "aliases",
+                nojson::array(|f| {
+                    for (id, paths) in &self.aliases {
+                        f.element(nojson::array(|f| {
+                            f.element(id.0)?;
+                            f.element(nojson::array(|f| {
+                                f.elements(paths.iter().map(|path| &path.0))
+                            }))
+                        }))?;
+                    }
+                    Ok(())
+                }),
+            )?;
+            f.member(
+                "external_reexports",
+                nojson::array(|f| {
+                    for reexport in &self.external_reexports {
+                        f.element(nojson::object(|f| {
+                            f.member("path", &reexport.path.0)?;
+                            f.member("crate_name", &reexport.crate_name)?;
+                            f.member("target_path", &reexport.target_path.0)?;
+                            f.member("is_glob", reexport.is_glob)
+                        }))?;
+                    }
+                    Ok(())
+                }),
+            )
+        })
+    }
+
+    /// Restores a doc from the output of [`CrateDoc::index`] without parsing the JSON
+    pub fn from_index(
+        path: std::path::PathBuf,
+        index: nojson::RawJsonValue<'_, '_>,
+    ) -> Result<Self, nojson::JsonParseError> {
+        let mut items = std::collections::HashMap::new();
+        for pair in index.to_member("items")?.required()?.to_array()? {
+            let [id, i]: [usize; 2] = pair.try_into()?;
+            items.insert(ItemId(id), JsonValueIndex::new(i));
+        }
+        let mut show_items = Vec::new();
+        for pair in index.to_member("show_items")?.required()?.to_array()? {
+            let mut pair = pair.to_array()?;
+            let (Some(path), Some(item)) = (pair.next(), pair.next()) else {
+                return Err(index.invalid("malformed show item"));
+            };
+            show_items.push((ItemPath(path.try_into()?), item_from_index(item)?));
+        }
+        let mut aliases = std::collections::HashMap::new();
+        for pair in index.to_member("aliases")?.required()?.to_array()? {
+            let mut pair = pair.to_array()?;
+            let (Some(id), Some(paths)) = (pair.next(), pair.next()) else {
+                return Err(index.invalid("malformed alias"));
+            };
+            let paths: Vec<Vec<String>> = paths.try_into()?;
+            aliases.insert(
+                ItemId(id.try_into()?),
+                paths.into_iter().map(ItemPath).collect(),
+            );
+        }
+        let mut external_reexports = Vec::new();
+        for reexport in index
+            .to_member("external_reexports")?
+            .required()?
+            .to_array()?
+        {
+            external_reexports.push(ExternalReexport {
+                path: ItemPath(reexport.to_member("path")?.required()?.try_into()?),
+                crate_name: reexport.to_member("crate_name")?.required()?.try_into()?,
+                target_path: ItemPath(reexport.to_member("target_path")?.required()?.try_into()?),
+                is_glob: reexport.to_member("is_glob")?.required()?.try_into()?,
+            });
+        }
+        let root_module_index: usize = index
+            .to_member("root_module_index")?
+            .required()?
+            .try_into()?;
+        Ok(Self {
+            path,
+            json: std::sync::OnceLock::new(),
+            format_version: index.to_member("format_version")?.required()?.try_into()?,
+            crate_name: index.to_member("crate_name")?.required()?.try_into()?,
+            items: CrateItems(items),
+            root_module_index: JsonValueIndex::new(root_module_index),
+            show_item_indices: show_item_indices(&show_items),
+            show_items,
+            public_item_count: index
+                .to_member("public_item_count")?
+                .required()?
+                .try_into()?,
+            aliases,
+            external_reexports,
+            function_sigs: None,
+        })
+    }
+
+    /// Returns the public paths of an item other than the canonical one (e.g., re-exports)
+    pub fn aliases(&self, item: &Item) -> &[ItemPath] {
+        self.aliases.get(&item.id).map_or(&[], |paths| paths)
+    }
+
+    /// Returns the canonical path of the item with the given id
+    ///
+    /// Items that are not public in this crate (e.g., items of other crates) are looked up
+    /// in the `paths` table.
+    pub fn item_path(
+        &self,
+        item_id_value: nojson::RawJsonValue<'_, '_>,
+    ) -> Result<Option<ItemPath>, nojson::JsonParseError> {
+        let item_id: ItemId = item_id_value.try_into()?;
+        if let Some(path) = self.canonical_path(item_id) {
+            return Ok(Some(path.clone()));
+        }
+        Ok(external_path(self.json(), item_id_value)?.map(|(_, path)| path))
+    }
+
+    /// Returns the canonical path of a public item of this crate
+    pub fn canonical_path(&self, item_id: ItemId) -> Option<&ItemPath> {
+        let &i = self.show_item_indices.get(&item_id)?;
+        Some(&self.show_items[i].0)
+    }
+
+    /// Returns the intra-doc links of an item as pairs of the link destination
+    /// (as written in the docs, e.g., `` `Foo::bar` ``) and the canonical path of its target
+    pub fn doc_links(
+        &self,
+        item: &Item,
+    ) -> Result<Vec<(String, ItemPath)>, nojson::JsonParseError> {
+        let Some(value) = self.items.get_by_id(self.json(), item.id) else {
+            return Ok(Vec::new());
         };
-        let mut collector = PublicItemCollector::new(&this.json, &this.items);
-        let root_module_value = this
-            .json
-            .get_value_by_index(this.root_module_index.get())
-            .expect("bug");
-        collector.collect(root_module_value)?;
-        this.show_items = collector.public_items;
-        this.public_item_count = this.show_items.len();
-        Ok(this)
+        let Some(links) = value.to_member("links")?.get() else {
+            return Ok(Vec::new());
+        };
+
+        let mut doc_links = Vec::new();
+        for (dest, item_id_value) in links.to_object()? {
+            if let Some(path) = self.item_path(item_id_value)? {
+                doc_links.push((dest.try_into()?, path));
+            }
+        }
+        Ok(doc_links)
+    }
+
+    /// Resolves a rustdoc-style relative HTML link (e.g., `struct.Foo.html` or
+    /// `../other/trait.Bar.html#method.baz`) written in the docs of the item at `path`
+    pub fn resolve_html_link(&self, path: &ItemPath, link: &str) -> Option<ItemPath> {
+        if link.contains("://") || link.starts_with('/') {
+            return None;
+        }
+        let (file, fragment) = link.split_once('#').unwrap_or((link, ""));
+
+        // Docs are shown on the page of the item, which is in the directory of its module
+        let mut segments = path.0.clone();
+        while segments.len() > 1
+            && !self
+                .show_items
+                .iter()
+                .any(|(p, x)| p.0 == segments && x.kind == ItemKind::Module)
+        {
+            segments.pop();
+        }
+
+        let (dirs, page) = file.rsplit_once('/').unwrap_or(("", file));
+        for dir in dirs.split('/').filter(|d| !d.is_empty() && *d != ".") {
+            if dir == ".." {
+                segments.pop()?;
+            } else {
+                segments.push(dir.to_owned());
+            }
+        }
+        if page != "index.html" {
+            let (kind, name) = page.strip_suffix(".html")?.split_once('.')?;
+            const PAGE_KINDS: &[&str] = &[
+                "struct",
+                "enum",
+                "union",
+                "trait",
+                "traitalias",
+                "fn",
+                "type",
+                "constant",
+                "static",
+                "macro",
+                "derive",
+                "attr",
+                "primitive",
+            ];
+            if !PAGE_KINDS.contains(&kind) {
+                return None;
+            }
+            segments.push(name.to_owned());
+        }
+        if let Some((kind, name)) = fragment.split_once('.') {
+            const FRAGMENT_KINDS: &[&str] = &[
+                "method",
+                "tymethod",
+                "variant",
+                "structfield",
+                "associatedtype",
+                "associatedconstant",
+            ];
+            if FRAGMENT_KINDS.contains(&kind) {
+                segments.push(name.to_owned());
+            }
+        }
+        (!segments.is_empty()).then_some(ItemPath(segments))
     }
+
+    /// Returns the items of this crate that `reexport` (made by another crate) refers to,
+    /// along with their paths in the re-exporting crate
+    pub fn resolve_reexport(&self, reexport: &ExternalReexport) -> Vec<(ItemPath, &Item)> {
+        let Some((target_path, target_item)) = self.show_items.iter().find(|(path, item)| {
+            *path == reexport.target_path || self.aliases(item).contains(&reexport.target_path)
+        }) else {
+            return Vec::new();
+        };
+
+        let mut resolved = Vec::new();
+        if !reexport.is_glob {
+            resolved.push((reexport.path.clone(), target_item));
+        }
+        for (path, item) in &self.show_items {
+            if path != target_path
+                && let Some(path) = path.rebase(target_path, &reexport.path)
+            {
+                resolved.push((path, item));
+            }
+        }
+        resolved
+    }
+}
+
+/// Encodes an item for [`CrateDoc::index`] (unlike rustdoc JSON, the JSON indices are kept)
+fn item_index(item: &Item) -> impl nojson::DisplayJson + '_ {
+    nojson::object(|f| {
+        f.member("id", item.id)?;
+        f.member("name", &item.name)?;
+        f.member("kind", item.kind.as_str())?;
+        f.member("is_public", item.is_public)?;
+        f.member("visibility_index", item.visibility_index.get())?;
+        f.member("docs_index", item.docs_index.map(JsonValueIndex::get))?;
+        f.member(
+            "deprecation_index",
+            item.deprecation_index.map(JsonValueIndex::get),
+        )?;
+        f.member("inner_index", item.inner_index.get())
+    })
+}
+
+fn item_from_index(value: nojson::RawJsonValue<'_, '_>) -> Result<Item, nojson::JsonParseError> {
+    let index = |name| -> Result<Option<JsonValueIndex>, nojson::JsonParseError> {
+        let index: Option<usize> = value.to_member(name)?.required()?.try_into()?;
+        Ok(index.map(JsonValueIndex::new))
+    };
+    let required_index = |name| -> Result<JsonValueIndex, nojson::JsonParseError> {
+        index(name)?.ok_or_else(|| value.invalid(format!("missing {name}")))
+    };
+    Ok(Item {
+        id: value.to_member("id")?.required()?.try_into()?,
+        name: value.to_member("name")?.required()?.try_into()?,
+        kind: value.to_member("kind")?.required()?.try_into()?,
+        is_public: value.to_member("is_public")?.required()?.try_into()?,
+        visibility_index: required_index("visibility_index")?,
+        docs_index: index("docs_index")?,
+        deprecation_index: index("deprecation_index")?,
+        inner_index: required_index("inner_index")?,
+    })
+}
+
+/// Parses rustdoc JSON, upgrading it to the current schema if it is older
+fn parse_json(text: &str) -> Result<(nojson::RawJsonOwned, u32), nojson::JsonParseError> {
+    let json = nojson::RawJsonOwned::parse(text)?;
+    let format_version = crate::compat::check_format_version(json.value())?;
+    let json = if crate::compat::needs_upgrade(format_version) {
+        nojson::RawJsonOwned::parse(crate::compat::upgrade(json.value()))?
+    } else {
+        json
+    };
+    Ok((json, format_version))
 }
 
 struct PublicItemCollector<'a> {
     json: &'a nojson::RawJsonOwned,
     items: &'a CrateItems,
     public_items: Vec<(ItemPath, Item)>,
+    aliases: std::collections::HashMap<ItemId, Vec<ItemPath>>,
+    visited: std::collections::HashSet<ItemId>,
+    expanding_globs: std::collections::HashSet<ItemId>,
+    external_reexports: Vec<ExternalReexport>,
+    /// Paths under which items are collected instead of the first path found (see [`Self::preferred_paths`])
+    preferred_paths: std::collections::HashMap<ItemId, ItemPath>,
 }
 
 impl<'a> PublicItemCollector<'a> {
@@ -349,6 +835,11 @@ impl<'a> PublicItemCollector<'a> {
             json,
             items,
             public_items: Vec::new(),
+            aliases: std::collections::HashMap::new(),
+            visited: std::collections::HashSet::new(),
+            expanding_globs: std::collections::HashSet::new(),
+            external_reexports: Vec::new(),
+            preferred_paths: std::collections::HashMap::new(),
         }
     }
 
@@ -361,11 +852,38 @@ impl<'a> PublicItemCollector<'a> {
         Ok(())
     }
 
+    /// Returns the items whose path in rustdoc's `paths` table is public but was not the first
+    /// path found (e.g., items defined in a public module but re-exported by an earlier item)
+    fn preferred_paths(
+        &self,
+    ) -> Result<std::collections::HashMap<ItemId, ItemPath>, nojson::JsonParseError> {
+        let mut preferred_paths = std::collections::HashMap::new();
+        for (id, aliases) in &self.aliases {
+            if let Some(path) = local_path(self.json, *id)?
+                && aliases.contains(&path)
+            {
+                preferred_paths.insert(*id, path);
+            }
+        }
+        Ok(preferred_paths)
+    }
+
     fn visit_item(
         &mut self,
         path: &mut ItemPath,
         item_value: nojson::RawJsonValue<'a, 'a>,
         force_public: bool,
+    ) -> Result<(), nojson::JsonParseError> {
+        self.visit_item_as(path, item_value, force_public, None)
+    }
+
+    /// Visits an item that is imported as `use_name` (if given) instead of its own name
+    fn visit_item_as(
+        &mut self,
+        path: &mut ItemPath,
+        item_value: nojson::RawJsonValue<'a, 'a>,
+        force_public: bool,
+        use_name: Option<&str>,
     ) -> Result<(), nojson::JsonParseError> {
         let item = Item::try_from(item_value)?;
 
@@ -373,7 +891,28 @@ impl<'a> PublicItemCollector<'a> {
             return Ok(());
         }
 
-        if let Some(name) = &item.name {
+        let name = use_name.map(str::to_owned).or_else(|| item.name.clone());
+        let is_deferred = name.as_ref().is_some_and(|name| {
+            self.preferred_paths.get(&item.id).is_some_and(|preferred| {
+                !self.visited.contains(&item.id) && path.join(name) != *preferred
+            })
+        });
+        if is_deferred || !self.visited.insert(item.id) {
+            // The item has already been (or will be) collected under its canonical path
+            if let Some(name) = name {
+                let alias = path.join(name);
+                let aliases = self.aliases.entry(item.id).or_default();
+                if !aliases.contains(&alias) {
+                    aliases.push(alias);
+                }
+            }
+            if item.kind == ItemKind::Use {
+                self.visit_use_target(path, &item)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(name) = &name {
             path.0.push(name.clone());
             self.public_items.push((path.clone(), item.clone()));
         }
@@ -381,9 +920,28 @@ impl<'a> PublicItemCollector<'a> {
         let inner = item.inner(self.json);
         match item.kind {
             ItemKind::Module => {
+                let mut child_values = Vec::new();
                 for item_id_value in inner.to_member("items")?.required()?.to_array()? {
-                    let item_value = self.items.get(self.json, item_id_value)?;
-                    self.visit_item(path, item_value, false)?;
+                    child_values.push(self.items.get(self.json, item_id_value)?);
+                }
+
+                // Explicitly named items shadow glob-imported ones
+                let mut bound_names = std::collections::HashSet::new();
+                for &child_value in &child_values {
+                    if let Some(name) = binding_name(child_value)? {
+                        bound_names.insert(name);
+                    }
+                }
+
+                for &child_value in &child_values {
+                    self.visit_item(path, child_value, false)?;
+                }
+                for &child_value in &child_values {
+                    if let Some(target_value) = self.glob_import_target(child_value)? {
+                        self.visit_glob_import(path, target_value, &mut bound_names)?;
+                    } else if let Some(reexport) = self.external_glob_import(path, child_value)? {
+                        self.external_reexports.push(reexport);
+                    }
                 }
             }
             ItemKind::Enum => {
@@ -429,6 +987,12 @@ impl<'a> PublicItemCollector<'a> {
                 }
             }
             ItemKind::Impl => {
+                // Impls generated by rustdoc are too noisy to list
+                if !is_generated_impl(inner)? {
+                    let impl_path = path.join(impl_path_segment(inner)?);
+                    self.public_items.push((impl_path, item.clone()));
+                }
+
                 for item_id_value in inner.to_member("items")?.required()?.to_array()? {
                     let item_value = self.items.get(self.json, item_id_value)?;
                     self.visit_item(path, item_value, false)?;
@@ -436,12 +1000,13 @@ impl<'a> PublicItemCollector<'a> {
             }
             ItemKind::Use => {
                 let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
-                if !is_glob {
-                    let target_id_value = inner.to_member("id")?.required()?;
-                    if let Ok(target_item_value) = self.items.get(self.json, target_id_value) {
-                        self.visit_item(path, target_item_value, false)?;
-                    }
-                }
+                let use_name: String = if is_glob {
+                    "*".to_owned()
+                } else {
+                    inner.to_member("name")?.required()?.try_into()?
+                };
+                self.public_items.push((path.join(use_name), item.clone()));
+                self.visit_use_target(path, &item)?;
             }
             // Leaf items with no children to visit
             ItemKind::Variant
@@ -458,10 +1023,556 @@ impl<'a> PublicItemCollector<'a> {
             | ItemKind::Primitive => {}
         }
 
-        if item.name.is_some() {
+        if name.is_some() {
             path.0.pop();
         }
 
         Ok(())
     }
+
+    /// Visits the item imported by a non-glob `use` (glob imports are expanded by the importing module)
+    fn visit_use_target(
+        &mut self,
+        path: &mut ItemPath,
+        use_item: &Item,
+    ) -> Result<(), nojson::JsonParseError> {
+        let inner = use_item.inner(self.json);
+        let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
+        if is_glob {
+            return Ok(());
+        }
+
+        let use_name: String = inner.to_member("name")?.required()?.try_into()?;
+        let target_id_value = inner.to_member("id")?.required()?;
+        if let Ok(target_item_value) = self.items.get(self.json, target_id_value) {
+            self.visit_item_as(path, target_item_value, false, Some(&use_name))?;
+        } else if let Some((crate_name, target_path)) = external_path(self.json, target_id_value)? {
+            self.external_reexports.push(ExternalReexport {
+                path: path.join(use_name),
+                crate_name,
+                target_path,
+                is_glob: false,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the re-export made by a public `use other_crate::path::*;` item
+    fn external_glob_import(
+        &self,
+        path: &ItemPath,
+        item_value: nojson::RawJsonValue<'a, 'a>,
+    ) -> Result<Option<ExternalReexport>, nojson::JsonParseError> {
+        let item = Item::try_from(item_value)?;
+        if item.kind != ItemKind::Use || !item.is_public {
+            return Ok(None);
+        }
+
+        let inner = item.inner(self.json);
+        let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
+        let target_id_value = inner.to_member("id")?.required()?;
+        if !is_glob || self.items.get(self.json, target_id_value).is_ok() {
+            return Ok(None);
+        }
+
+        let reexport =
+            external_path(self.json, target_id_value)?.map(|(crate_name, target_path)| {
+                ExternalReexport {
+                    path: path.clone(),
+                    crate_name,
+                    target_path,
+                    is_glob: true,
+                }
+            });
+        Ok(reexport)
+    }
+
+    /// Returns the module or enum imported by a public `use path::*;` item
+    fn glob_import_target(
+        &self,
+        item_value: nojson::RawJsonValue<'a, 'a>,
+    ) -> Result<Option<nojson::RawJsonValue<'a, 'a>>, nojson::JsonParseError> {
+        let item = Item::try_from(item_value)?;
+        if item.kind != ItemKind::Use || !item.is_public {
+            return Ok(None);
+        }
+
+        let inner = item.inner(self.json);
+        let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
+        if !is_glob {
+            return Ok(None);
+        }
+
+        let target_id_value = inner.to_member("id")?.required()?;
+        Ok(self.items.get(self.json, target_id_value).ok())
+    }
+
+    /// Visits the public children of a glob-imported module or enum as if they were
+    /// defined in the importing module, skipping names that are already bound there
+    fn visit_glob_import(
+        &mut self,
+        path: &mut ItemPath,
+        target_value: nojson::RawJsonValue<'a, 'a>,
+        bound_names: &mut std::collections::HashSet<String>,
+    ) -> Result<(), nojson::JsonParseError> {
+        let target = Item::try_from(target_value)?;
+        let inner = target.inner(self.json);
+        let member_name = match target.kind {
+            ItemKind::Module => "items",
+            ItemKind::Enum => "variants",
+            _ => return Ok(()),
+        };
+
+        // Glob imports can form cycles (e.g., `pub use super::*;` in both directions)
+        if !self.expanding_globs.insert(target.id) {
+            return Ok(());
+        }
+
+        let mut nested_targets = Vec::new();
+        for item_id_value in inner.to_member(member_name)?.required()?.to_array()? {
+            let Ok(child_value) = self.items.get(self.json, item_id_value) else {
+                continue;
+            };
+            let child = Item::try_from(child_value)?;
+            if !child.is_public {
+                continue;
+            }
+
+            if let Some(nested_target) = self.glob_import_target(child_value)? {
+                nested_targets.push(nested_target);
+                continue;
+            }
+            if let Some(name) = binding_name(child_value)?
+                && !bound_names.insert(name)
+            {
+                continue;
+            }
+            self.visit_item(path, child_value, false)?;
+        }
+
+        // Items imported by nested globs are shadowed by the target's own items
+        for nested_target in nested_targets {
+            self.visit_glob_import(path, nested_target, bound_names)?;
+        }
+
+        self.expanding_globs.remove(&target.id);
+        Ok(())
+    }
+}
+
+/// Looks up the crate name and path of an item that is not in this crate's `index`
+/// using the `paths` and `external_crates` tables
+fn external_path(
+    json: &nojson::RawJsonOwned,
+    item_id_value: nojson::RawJsonValue<'_, '_>,
+) -> Result<Option<(String, ItemPath)>, nojson::JsonParseError> {
+    if !item_id_value.kind().is_integer() {
+        return Ok(None);
+    }
+    let root = json.value();
+    let Some(paths) = root.to_member("paths")?.get() else {
+        return Ok(None);
+    };
+    let Some(summary) = paths.to_member(item_id_value.as_raw_str())?.get() else {
+        return Ok(None);
+    };
+
+    let crate_id = summary.to_member("crate_id")?.required()?;
+    let mut segments: Vec<String> = summary.to_member("path")?.required()?.try_into()?;
+    if segments.is_empty() {
+        return Ok(None);
+    }
+    if let Some(external_crates) = root.to_member("external_crates")?.get()
+        && let Some(external_crate) = external_crates.to_member(crate_id.as_raw_str())?.get()
+    {
+        segments[0] = external_crate.to_member("name")?.required()?.try_into()?;
+    }
+
+    let crate_name = segments[0].clone();
+    Ok(Some((crate_name, ItemPath(segments))))
+}
+
+fn show_item_indices(show_items: &[(ItemPath, Item)]) -> std::collections::HashMap<ItemId, usize> {
+    let mut indices = std::collections::HashMap::new();
+    for (i, (_, item)) in show_items.iter().enumerate() {
+        indices.entry(item.id).or_insert(i);
+    }
+    indices
+}
+
+/// Returns the path of an item of this crate given by rustdoc's `paths` table
+fn local_path(
+    json: &nojson::RawJsonOwned,
+    id: ItemId,
+) -> Result<Option<ItemPath>, nojson::JsonParseError> {
+    let Some(paths) = json.value().to_member("paths")?.get() else {
+        return Ok(None);
+    };
+    let Some(summary) = paths.to_member(&id.to_string())?.get() else {
+        return Ok(None);
+    };
+    let crate_id: u32 = summary.to_member("crate_id")?.required()?.try_into()?;
+    if crate_id != 0 {
+        return Ok(None);
+    }
+    Ok(Some(ItemPath(
+        summary.to_member("path")?.required()?.try_into()?,
+    )))
+}
+
+/// Returns the name that an item binds in its parent module (`None` for glob imports and impls)
+fn binding_name(
+    item_value: nojson::RawJsonValue<'_, '_>,
+) -> Result<Option<String>, nojson::JsonParseError> {
+    let item = Item::try_from(item_value)?;
+    if item.kind != ItemKind::Use {
+        return Ok(item.name);
+    }
+
+    let inner = item_value
+        .to_member("inner")?
+        .required()?
+        .to_member("use")?
+        .required()?;
+    let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
+    if is_glob {
+        return Ok(None);
+    }
+    Ok(Some(inner.to_member("name")?.required()?.try_into()?))
+}
+
+/// Returns `true` for impls generated by rustdoc (auto traits and blanket impls)
+pub fn is_generated_impl(
+    inner: nojson::RawJsonValue<'_, '_>,
+) -> Result<bool, nojson::JsonParseError> {
+    let is_synthetic: bool = inner.to_member("is_synthetic")?.required()?.try_into()?;
+    let is_blanket = inner
+        .to_member("blanket_impl")?
+        .get()
+        .is_some_and(|v| !v.kind().is_null());
+    Ok(is_synthetic || is_blanket)
+}
+
+/// Returns the path segment of an impl (e.g., `impl`, `impl From<u8>` or `impl !Send`)
+///
+/// The `for` type is only included if it is not the parent type with its own generic params,
+/// so that the segments of the impls of a type are distinct.
+fn impl_path_segment(
+    inner: nojson::RawJsonValue<'_, '_>,
+) -> Result<String, nojson::JsonParseError> {
+    // Paths are built (and cached) before `--strict` is known, so unsupported JSON is kept as-is
+    // here and reported when the impl header itself is formatted
+    let format =
+        |f: &dyn Fn(&mut crate::format_type::TypeFormatter<&mut Vec<u8>>) -> crate::Result<()>| {
+            let mut buffer = Vec::new();
+            f(&mut crate::format_type::TypeFormatter::without_doc(
+                &mut buffer,
+            ))
+            .map_err(|e| inner.invalid(e.to_string()))?;
+            Ok::<_, nojson::JsonParseError>(String::from_utf8(buffer).expect("bug"))
+        };
+
+    let mut segment = "impl".to_owned();
+    let trait_info = inner.to_member("trait")?.required()?;
+    if !trait_info.kind().is_null() {
+        let is_negative: bool = inner.to_member("is_negative")?.required()?.try_into()?;
+        let trait_path = format(&|f| f.format_path(trait_info))?;
+        // Only the last segment of the trait path (e.g., `From<u8>` for `core::convert::From<u8>`)
+        let name_end = trait_path.find('<').unwrap_or(trait_path.len());
+        let name_start = trait_path[..name_end].rfind("::").map_or(0, |i| i + 2);
+        segment.push(' ');
+        if is_negative {
+            segment.push('!');
+        }
+        segment.push_str(&trait_path[name_start..]);
+    }
+
+    let for_type = inner.to_member("for")?.required()?;
+    if !is_generic_self_type(for_type)? {
+        segment.push_str(if trait_info.kind().is_null() {
+            " "
+        } else {
+            " for "
+        });
+        segment.push_str(&format(&|f| f.format(for_type))?);
+    }
+    Ok(segment)
+}
+
+/// Returns whether `ty` is a path whose generic args are all generic params (e.g., `S<'a, T>`)
+fn is_generic_self_type(ty: nojson::RawJsonValue<'_, '_>) -> Result<bool, nojson::JsonParseError> {
+    let Some(resolved) = ty.to_member("resolved_path")?.get() else {
+        return Ok(false);
+    };
+    let Some(args) = resolved.to_member("args")?.get() else {
+        return Ok(true);
+    };
+    if args.kind().is_null() {
+        return Ok(true);
+    }
+    let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() else {
+        return Ok(false);
+    };
+    if angle_bracketed
+        .to_member("constraints")?
+        .get()
+        .is_some_and(|constraints| constraints.to_array().is_ok_and(|mut c| c.next().is_some()))
+    {
+        return Ok(false);
+    }
+    for arg in angle_bracketed.to_member("args")?.required()?.to_array()? {
+        let is_param = if let Some(ty) = arg.to_member("type")?.get() {
+            ty.to_member("generic")?.get().is_some()
+        } else {
+            arg.to_member("lifetime")?.get().is_some()
+        };
+        if !is_param {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_doc_links() -> Result<(), nojson::JsonParseError> {
+        let text = r#"{"format_version": 57, "root": 0, "paths": {"9": {"crate_id": 1, "path": ["core", "option", "Option"], "kind": "enum"}}, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "sub", "visibility": "public", "inner": {"module": {"items": [2]}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": "A", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": "[`B`] and [`Option`]", "links": {"`B`": 1, "`Option`": 9}, "deprecation": null}
+        }}"#;
+        let doc = CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+        let (path, item) = &doc.show_items[2];
+
+        let links: Vec<_> = doc
+            .doc_links(item)?
+            .into_iter()
+            .map(|(dest, target)| format!("{dest} => {target}"))
+            .collect();
+        assert_eq!(links, ["`B` => t::sub", "`Option` => core::option::Option"]);
+
+        let resolve = |link| doc.resolve_html_link(path, link).map(|x| x.to_string());
+        assert_eq!(resolve("struct.C.html").as_deref(), Some("t::sub::C"));
+        assert_eq!(
+            resolve("../trait.T.html#method.m").as_deref(),
+            Some("t::T::m")
+        );
+        assert_eq!(resolve("index.html").as_deref(), Some("t::sub"));
+        assert_eq!(resolve("https://example.com/struct.C.html"), None);
+        assert_eq!(resolve("C"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn collect_glob_reexports() -> Result<(), nojson::JsonParseError> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2, 3, 6, 8]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "inner", "visibility": "public", "inner": {"module": {"items": [4, 5]}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": null, "visibility": "public", "inner": {"use": {"source": "inner", "name": "inner", "id": 1, "is_glob": true}}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": "B", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": "A", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "5": {"id": 5, "name": "B", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "6": {"id": 6, "name": "E", "visibility": "public", "inner": {"enum": {"variants": [7], "impls": []}}, "docs": null, "deprecation": null},
+            "7": {"id": 7, "name": "X", "visibility": "default", "inner": {"variant": {"kind": "plain"}}, "docs": null, "deprecation": null},
+            "8": {"id": 8, "name": null, "visibility": "public", "inner": {"use": {"source": "E", "name": "E", "id": 6, "is_glob": true}}, "docs": null, "deprecation": null}
+        }}"#;
+        let doc = CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+
+        let paths: Vec<_> = doc
+            .show_items
+            .iter()
+            .map(|(path, item)| format!("[{}] {}", item.kind.as_keyword_str(), path))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "[mod] t",
+                "[mod] t::inner",
+                "[struct] t::inner::A",
+                "[struct] t::inner::B",
+                "[use] t::*",
+                "[struct] t::B",
+                "[enum] t::E",
+                "[variant] t::E::X",
+                "[use] t::*",
+            ]
+        );
+
+        let alias_of = |id| {
+            doc.aliases
+                .get(&ItemId(id))
+                .map(|paths| paths[0].to_string())
+        };
+        assert_eq!(alias_of(4).as_deref(), Some("t::A"));
+        assert_eq!(alias_of(7).as_deref(), Some("t::X"));
+        assert_eq!(alias_of(5), None);
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_external_reexports() -> Result<(), nojson::JsonParseError> {
+        let facade_text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "facade", "visibility": "public", "inner": {"module": {"items": [1, 2]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": null, "visibility": "public", "inner": {"use": {"source": "core_x::Thing", "name": "Thing", "id": 10, "is_glob": false}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": null, "visibility": "public", "inner": {"use": {"source": "core_x::sub", "name": "sub", "id": 11, "is_glob": true}}, "docs": null, "deprecation": null}
+        }, "paths": {
+            "10": {"crate_id": 1, "path": ["core_x", "Thing"], "kind": "struct"},
+            "11": {"crate_id": 1, "path": ["core_x", "sub"], "kind": "module"}
+        }, "external_crates": {
+            "1": {"name": "core_x", "html_root_url": null}
+        }}"#;
+        let facade = CrateDoc::parse(std::path::PathBuf::from(""), facade_text)?;
+        assert_eq!(facade.external_reexports.len(), 2);
+
+        let core_text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "core_x", "visibility": "public", "inner": {"module": {"items": [1, 2]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "Thing", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": "sub", "visibility": "public", "inner": {"module": {"items": [3]}}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": "g", "visibility": "public", "inner": {"function": {}}, "docs": null, "deprecation": null}
+        }}"#;
+        let core = CrateDoc::parse(std::path::PathBuf::from(""), core_text)?;
+
+        let resolved: Vec<_> = facade
+            .external_reexports
+            .iter()
+            .flat_map(|reexport| core.resolve_reexport(reexport))
+            .map(|(path, item)| format!("{} => {}", path, item.name.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(resolved, ["facade::Thing => Thing", "facade::g => g"]);
+        Ok(())
+    }
+
+    #[test]
+    fn collect_cyclic_reexports() -> Result<(), nojson::JsonParseError> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2, 3]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "inner", "visibility": "public", "inner": {"module": {"items": [4, 5]}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": null, "visibility": "public", "inner": {"use": {"source": "crate", "name": "foo", "id": 0, "is_glob": false}}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": null, "visibility": "public", "inner": {"use": {"source": "inner", "name": "inner", "id": 1, "is_glob": true}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": null, "visibility": "public", "inner": {"use": {"source": "super", "name": "super", "id": 0, "is_glob": true}}, "docs": null, "deprecation": null},
+            "5": {"id": 5, "name": "A", "visibility": "public", "inner": {"struct": {"kind": "unit", "impls": []}}, "docs": null, "deprecation": null}
+        }}"#;
+        let doc = CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+
+        let paths: Vec<_> = doc
+            .show_items
+            .iter()
+            .filter(|(_, item)| item.kind.is_shown_by_default())
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(paths, ["t", "t::inner", "t::inner::A"]);
+
+        let aliases: Vec<_> = doc.aliases[&ItemId(0)]
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        assert_eq!(aliases, ["t::inner::foo", "t::foo"]);
+        Ok(())
+    }
+
+    #[test]
+    fn prefer_paths_of_paths_table() -> Result<(), nojson::JsonParseError> {
+        // `A` is re-exported by `t` before `t::inner`, where it is defined, is visited
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [2, 1]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "inner", "visibility": "public", "inner": {"module": {"items": [5]}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": null, "visibility": "public", "inner": {"use": {"source": "inner::A", "name": "A", "id": 5, "is_glob": false}}, "docs": null, "deprecation": null},
+            "5": {"id": 5, "name": "A", "visibility": "public", "inner": {"struct": {"kind": {"plain": {"fields": [6], "has_stripped_fields": false}}, "impls": []}}, "docs": null, "deprecation": null},
+            "6": {"id": 6, "name": "x", "visibility": "public", "inner": {"struct_field": {"primitive": "u8"}}, "docs": null, "deprecation": null}
+        }, "paths": {"5": {"crate_id": 0, "path": ["t", "inner", "A"], "kind": "struct"}}}"#;
+        let doc = CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+
+        let paths: Vec<_> = doc
+            .show_items
+            .iter()
+            .filter(|(_, item)| item.kind != ItemKind::Use)
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(paths, ["t", "t::inner", "t::inner::A", "t::inner::A::x"]);
+        let aliases: Vec<_> = doc.aliases[&ItemId(5)]
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        assert_eq!(aliases, ["t::A"]);
+        Ok(())
+    }
+
+    #[test]
+    fn peek_crate_names() {
+        let text = r#"{"root": 7, "crate_version": null, "index": {
+            "3": {"id": 3, "name": "x{\"}", "inner": {"function": {"sig": [[], null]}}},
+            "7" : {"id": 7, "name": "my_crate", "inner": {"module": {"items": [3]}}}
+        }, "format_version": 57}"#;
+        assert_eq!(CrateDoc::peek_crate_name(text).as_deref(), Some("my_crate"));
+
+        // `index` before `root` cannot be scanned in one pass
+        let text = r#"{"index": {"0": {"name": "a"}}, "root": 0}"#;
+        assert_eq!(CrateDoc::peek_crate_name(text), None);
+        assert_eq!(CrateDoc::peek_crate_name("[1, 2]"), None);
+    }
+
+    #[test]
+    fn distinct_impl_paths() -> Result<(), nojson::JsonParseError> {
+        let impl_item = |id: u32, trait_path: &str, args: &str, for_type: &str| {
+            let trait_info = if trait_path.is_empty() {
+                "null".to_owned()
+            } else {
+                format!(r#"{{"path": "{trait_path}", "id": 90, "args": {args}}}"#)
+            };
+            format!(
+                r#""{id}": {{"id": {id}, "name": null, "visibility": "default", "docs": null, "deprecation": null, "inner": {{"impl": {{"is_unsafe": false, "generics": {{"params": [], "where_predicates": []}}, "provided_trait_methods": [], "trait": {trait_info}, "for": {for_type}, "items": [], "is_negative": false, "is_synthetic": false, "blanket_impl": null}}}}}}"#
+            )
+        };
+        let u8_args =
+            r#"{"angle_bracketed": {"args": [{"type": {"primitive": "u8"}}], "constraints": []}}"#;
+        let u16_args =
+            r#"{"angle_bracketed": {"args": [{"type": {"primitive": "u16"}}], "constraints": []}}"#;
+        let t_args =
+            r#"{"angle_bracketed": {"args": [{"type": {"generic": "T"}}], "constraints": []}}"#;
+        let s = r#"{"resolved_path": {"path": "S", "id": 1, "args": null}}"#;
+        let g_t = format!(r#"{{"resolved_path": {{"path": "G", "id": 2, "args": {t_args}}}}}"#);
+        let g_u8 = format!(r#"{{"resolved_path": {{"path": "G", "id": 2, "args": {u8_args}}}}}"#);
+        let ref_s = format!(
+            r#"{{"borrowed_ref": {{"lifetime": null, "is_mutable": false, "type": {s}}}}}"#
+        );
+        let text = format!(
+            r#"{{"format_version": 57, "root": 0, "index": {{
+            "0": {{"id": 0, "name": "t", "visibility": "public", "inner": {{"module": {{"items": [1, 2]}}}}, "docs": null, "deprecation": null}},
+            "1": {{"id": 1, "name": "S", "visibility": "public", "inner": {{"struct": {{"kind": "unit", "impls": [10, 11, 12, 13]}}}}, "docs": null, "deprecation": null}},
+            "2": {{"id": 2, "name": "G", "visibility": "public", "inner": {{"struct": {{"kind": "unit", "impls": [14, 15, 16]}}}}, "docs": null, "deprecation": null}},
+            {}, {}, {}, {}, {}, {}, {}
+        }}}}"#,
+            impl_item(10, "", "null", s),
+            impl_item(11, "core::convert::From", u8_args, s),
+            impl_item(12, "From", u16_args, s),
+            impl_item(13, "Clone", "null", &ref_s),
+            impl_item(14, "Tr", "null", &g_t),
+            impl_item(15, "Tr", "null", &g_u8),
+            impl_item(16, "", "null", &g_u8),
+        );
+        let doc = CrateDoc::parse(std::path::PathBuf::from(""), &text)?;
+
+        let paths: Vec<_> = doc
+            .show_items
+            .iter()
+            .filter(|(_, item)| item.kind == ItemKind::Impl)
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "t::S::impl",
+                "t::S::impl From<u8>",
+                "t::S::impl From<u16>",
+                "t::S::impl Clone for &S",
+                "t::G::impl Tr",
+                "t::G::impl Tr for G<u8>",
+                "t::G::impl G<u8>",
+            ]
+        );
+        Ok(())
+    }
 }
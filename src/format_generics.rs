@@ -0,0 +1,225 @@
+pub fn format_params_to_string(
+    doc: &crate::doc::CrateDoc,
+    generics: nojson::RawJsonValue,
+) -> crate::Result<String> {
+    let mut buffer = Vec::new();
+    let mut formatter = GenericsFormatter::new(&mut buffer, doc);
+    formatter.format_params(generics)?;
+    Ok(String::from_utf8(buffer).expect("bug"))
+}
+
+/// Formats the where clause as `\nwhere\n    A: X,\n    B: Y` (empty if there are no predicates)
+pub fn format_where_clause_to_string(
+    doc: &crate::doc::CrateDoc,
+    generics: nojson::RawJsonValue,
+) -> crate::Result<String> {
+    let mut buffer = Vec::new();
+    let mut formatter = GenericsFormatter::new(&mut buffer, doc);
+    formatter.format_where_clause(generics)?;
+    Ok(String::from_utf8(buffer).expect("bug"))
+}
+
+/// Formats the `generics` of items (parameter lists and where clauses)
+#[derive(Debug)]
+pub struct GenericsFormatter<'a, W> {
+    writer: W,
+    doc: &'a crate::doc::CrateDoc,
+    strict: bool,
+}
+
+impl<'a, W: std::io::Write> GenericsFormatter<'a, W> {
+    pub fn new(writer: W, doc: &'a crate::doc::CrateDoc) -> Self {
+        Self {
+            writer,
+            doc,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Writes `<'a, T: Bound = Default, const N: usize>` (nothing if there are no params)
+    pub fn format_params(&mut self, generics: nojson::RawJsonValue) -> crate::Result<()> {
+        let mut params = Vec::new();
+        for param in generics.to_member("params")?.required()?.to_array()? {
+            // `impl Trait` in argument position is represented as a synthetic type param
+            if let Some(ty) = param
+                .to_member("kind")?
+                .required()?
+                .to_member("type")?
+                .get()
+                && let Some(is_synthetic) = ty.to_member("is_synthetic")?.get()
+                && bool::try_from(is_synthetic)?
+            {
+                continue;
+            }
+            params.push(param);
+        }
+        if params.is_empty() {
+            return Ok(());
+        }
+
+        write!(self.writer, "<")?;
+        for (i, param) in params.into_iter().enumerate() {
+            if i > 0 {
+                write!(self.writer, ", ")?;
+            }
+            self.format_param(param)?;
+        }
+        write!(self.writer, ">")?;
+        Ok(())
+    }
+
+    fn format_param(&mut self, param: nojson::RawJsonValue) -> crate::Result<()> {
+        let name = param
+            .to_member("name")?
+            .required()?
+            .to_unquoted_string_str()?;
+        let kind = param.to_member("kind")?.required()?;
+
+        if let Some(lifetime) = kind.to_member("lifetime")?.get() {
+            write!(self.writer, "{}", name)?;
+            let outlives: Vec<_> = lifetime
+                .to_member("outlives")?
+                .required()?
+                .to_array()?
+                .map(|x| x.to_unquoted_string_str())
+                .collect::<Result<_, _>>()?;
+            if !outlives.is_empty() {
+                write!(self.writer, ": {}", outlives.join(" + "))?;
+            }
+        } else if let Some(ty) = kind.to_member("type")?.get() {
+            write!(self.writer, "{}", name)?;
+            let bounds = ty.to_member("bounds")?.required()?;
+            if bounds.to_array()?.next().is_some() {
+                write!(self.writer, ": ")?;
+                self.type_formatter().format_bounds(bounds)?;
+            }
+            if let Some(default) = ty.to_member("default")?.get()
+                && !default.kind().is_null()
+            {
+                write!(self.writer, " = ")?;
+                self.type_formatter().format(default)?;
+            }
+        } else if let Some(constant) = kind.to_member("const")?.get() {
+            write!(self.writer, "const {}: ", name)?;
+            self.type_formatter()
+                .format(constant.to_member("type")?.required()?)?;
+            if let Some(default) = constant.to_member("default")?.get()
+                && !default.kind().is_null()
+            {
+                write!(self.writer, " = {}", default.to_unquoted_string_str()?)?;
+            }
+        } else {
+            write!(self.writer, "{}", name)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `\nwhere\n    A: X,\n    B: Y` and returns whether there were any predicates
+    ///
+    /// The last predicate has no trailing comma so that callers can decide how to end the clause.
+    pub fn format_where_clause(&mut self, generics: nojson::RawJsonValue) -> crate::Result<bool> {
+        let predicates: Vec<_> = generics
+            .to_member("where_predicates")?
+            .required()?
+            .to_array()?
+            .collect();
+        if predicates.is_empty() {
+            return Ok(false);
+        }
+
+        write!(self.writer, "\nwhere")?;
+        for (i, predicate) in predicates.into_iter().enumerate() {
+            if i > 0 {
+                write!(self.writer, ",")?;
+            }
+            write!(self.writer, "\n    ")?;
+            self.format_where_predicate(predicate)?;
+        }
+        Ok(true)
+    }
+
+    fn format_where_predicate(&mut self, predicate: nojson::RawJsonValue) -> crate::Result<()> {
+        if let Some(bound_predicate) = predicate.to_member("bound_predicate")?.get() {
+            if let Some(params) = bound_predicate.to_member("generic_params")?.get() {
+                self.type_formatter().format_higher_ranked_params(params)?;
+            }
+            self.type_formatter()
+                .format(bound_predicate.to_member("type")?.required()?)?;
+            let bounds = bound_predicate.to_member("bounds")?.required()?;
+            if bounds.to_array()?.next().is_some() {
+                write!(self.writer, ": ")?;
+                self.type_formatter().format_bounds(bounds)?;
+            }
+        } else if let Some(lifetime_predicate) = predicate.to_member("lifetime_predicate")?.get() {
+            let lifetime = lifetime_predicate
+                .to_member("lifetime")?
+                .required()?
+                .to_unquoted_string_str()?;
+            let outlives: Vec<_> = lifetime_predicate
+                .to_member("outlives")?
+                .required()?
+                .to_array()?
+                .map(|x| x.to_unquoted_string_str())
+                .collect::<Result<_, _>>()?;
+            write!(self.writer, "{}: {}", lifetime, outlives.join(" + "))?;
+        } else if let Some(eq_predicate) = predicate.to_member("eq_predicate")?.get() {
+            self.type_formatter()
+                .format(eq_predicate.to_member("lhs")?.required()?)?;
+            write!(self.writer, " = ")?;
+            self.type_formatter()
+                .format_term(eq_predicate.to_member("rhs")?.required()?)?;
+        } else {
+            self.type_formatter()
+                .format_unsupported(predicate, "where predicate")?;
+        }
+        Ok(())
+    }
+
+    fn type_formatter(&mut self) -> crate::format_type::TypeFormatter<'a, &mut W> {
+        crate::format_type::TypeFormatter::new(&mut self.writer, self.doc).strict(self.strict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_params_and_where_clause() -> crate::Result<()> {
+        let doc = crate::doc::CrateDoc::parse(
+            std::path::PathBuf::from(""),
+            r#"{"format_version": 57, "root": 0, "index": {"0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": []}}, "docs": null, "deprecation": null}}}"#,
+        )?;
+        let json = nojson::RawJson::parse(
+            r#"{
+                "params": [
+                    {"name": "'a", "kind": {"lifetime": {"outlives": ["'b"]}}},
+                    {"name": "T", "kind": {"type": {"bounds": [{"trait_bound": {"trait": {"path": "Clone", "id": 1, "args": null}, "generic_params": [], "modifier": "none"}}, {"trait_bound": {"trait": {"path": "Sized", "id": 2, "args": null}, "generic_params": [], "modifier": "maybe"}}], "default": null, "is_synthetic": false}}},
+                    {"name": "A", "kind": {"type": {"bounds": [], "default": {"resolved_path": {"path": "Global", "id": 3, "args": null}}, "is_synthetic": false}}},
+                    {"name": "impl Display", "kind": {"type": {"bounds": [], "default": null, "is_synthetic": true}}},
+                    {"name": "N", "kind": {"const": {"type": {"primitive": "usize"}, "default": "3"}}}
+                ],
+                "where_predicates": [
+                    {"bound_predicate": {"type": {"generic": "F"}, "bounds": [{"trait_bound": {"trait": {"path": "Fn", "id": 4, "args": null}, "generic_params": [{"name": "'c", "kind": {"lifetime": {"outlives": []}}}], "modifier": "none"}}], "generic_params": []}},
+                    {"lifetime_predicate": {"lifetime": "'a", "outlives": ["'static"]}}
+                ]
+            }"#,
+        )?;
+        let generics = json.value();
+
+        assert_eq!(
+            format_params_to_string(&doc, generics)?,
+            "<'a: 'b, T: Clone + ?Sized, A = Global, const N: usize = 3>"
+        );
+        assert_eq!(
+            format_where_clause_to_string(&doc, generics)?,
+            "\nwhere\n    F: for<'c> Fn,\n    'a: 'static"
+        );
+        Ok(())
+    }
+}
@@ -1,14 +1,83 @@
+/// Formats the signature of any item kind (as shown in item details)
+pub fn format_signature_to_string(
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+    strict: bool,
+) -> crate::Result<String> {
+    let inner = item.inner(doc.json());
+
+    let signature = match item.kind {
+        crate::doc::ItemKind::TypeAlias | crate::doc::ItemKind::AssocType => {
+            let kw = item.kind.as_keyword_str();
+            let view = crate::item_view::TypeView::new(doc, item).strict(strict);
+            if let Some(ty) = view.ty()? {
+                format!("{kw} {} = {};", view.name()?, ty)
+            } else {
+                format!("{kw} {};", view.name()?)
+            }
+        }
+        crate::doc::ItemKind::TraitAlias => {
+            let kw = item.kind.as_keyword_str();
+            let name = item.name.as_ref().expect("bug");
+            format!("{kw} {} = {};", name, inner)
+        }
+        crate::doc::ItemKind::Primitive => {
+            let view = crate::item_view::PrimitiveView::new(doc, item);
+            format!("type {};", view.name())
+        }
+        crate::doc::ItemKind::Constant | crate::doc::ItemKind::AssocConst => {
+            let view = crate::item_view::ConstantView::new(doc, item).strict(strict);
+            format!("const {}: {};", view.name(), view.ty()?)
+        }
+        crate::doc::ItemKind::Module => {
+            let view = crate::item_view::ModuleView::new(doc, item);
+            let child_count = view.child_count()?;
+            format!("mod {} {{ /* {} items */ }}", view.name(), child_count)
+        }
+        crate::doc::ItemKind::Macro => inner.to_unquoted_string_str()?.into_owned(),
+        crate::doc::ItemKind::ProcMacro => {
+            let view = crate::item_view::ProcMacroView::new(doc, item);
+            view.derive_attribute()?
+        }
+        crate::doc::ItemKind::StructField => {
+            let view = crate::item_view::FieldView::new(doc, item).strict(strict);
+            format!("  {}: {}", view.name(), view.ty()?)
+        }
+        crate::doc::ItemKind::Function => {
+            let view = crate::item_view::FunctionView::new(doc, item).strict(strict);
+            view.signature()?
+        }
+        crate::doc::ItemKind::Static => {
+            let view = crate::item_view::ConstantView::new(doc, item).strict(strict);
+            format!("static {}: {};", view.name(), view.ty()?)
+        }
+        crate::doc::ItemKind::Variant => format_enum_variant_to_string(doc, item, strict)?,
+        crate::doc::ItemKind::Enum => format_enum_to_string(doc, item, strict)?,
+        crate::doc::ItemKind::Trait => format_trait_to_string(doc, item, strict)?,
+        crate::doc::ItemKind::Struct => format_struct_to_string(doc, item, strict)?,
+        crate::doc::ItemKind::Union => format_union_to_string(doc, item, strict)?,
+        crate::doc::ItemKind::Impl => {
+            let view = crate::item_view::ImplView::new(doc, item).strict(strict);
+            view.signature()?
+        }
+        crate::doc::ItemKind::Use => format_use_to_string(doc, item)?,
+    };
+
+    Ok(signature)
+}
+
 pub fn format_trait_to_string(
     doc: &crate::doc::CrateDoc,
     item: &crate::doc::Item,
+    strict: bool,
 ) -> crate::Result<String> {
     let name = item.name.as_ref().expect("bug");
-    let inner = item.inner(&doc.json);
+    let inner = item.inner(doc.json());
     let mut buffer = Vec::new();
-    let mut formatter = TraitFormatter::new(&mut buffer, doc, name);
+    let mut formatter = TraitFormatter::new(&mut buffer, doc, name).strict(strict);
     formatter
         .format(inner)
-        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json.text()))?;
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
 
@@ -16,12 +85,23 @@ pub fn format_trait_to_string(
 pub struct TraitFormatter<'a, W> {
     writer: W,
     doc: &'a crate::doc::CrateDoc,
+    strict: bool,
     name: &'a str,
 }
 
 impl<'a, W: std::io::Write> TraitFormatter<'a, W> {
     pub fn new(writer: W, doc: &'a crate::doc::CrateDoc, name: &'a str) -> Self {
-        Self { writer, doc, name }
+        Self {
+            writer,
+            doc,
+            strict: false,
+            name,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
@@ -38,48 +118,30 @@ impl<'a, W: std::io::Write> TraitFormatter<'a, W> {
 
         write!(self.writer, "trait {}", self.name)?;
 
-        // Format generics
-        let generics = inner.to_member("generics")?;
-        if let Some(g) = generics.get() {
-            self.format_generics(g)?;
+        let generics = inner.to_member("generics")?.get();
+        if let Some(g) = generics {
+            self.generics_formatter().format_params(g)?;
         }
 
         // Format bounds (supertraits)
-        let bounds = inner.to_member("bounds")?;
-        if let Some(bounds_array) = bounds.get() {
-            let bounds_list: Vec<_> = bounds_array.to_array()?.collect();
-            if !bounds_list.is_empty() {
-                write!(self.writer, ": ")?;
-                for (i, bound) in bounds_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, " + ")?;
-                    }
-
-                    if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
-                        let trait_info = trait_bound.to_member("trait")?.required()?;
-                        let trait_path = trait_info
-                            .to_member("path")?
-                            .required()?
-                            .to_unquoted_string_str()?;
-                        write!(self.writer, "{}", trait_path)?;
-
-                        // Format trait generic args if present
-                        if let Some(args) = trait_info.to_member("args")?.get()
-                            && !args.kind().is_null()
-                        {
-                            self.format_trait_args(args)?;
-                        }
-                    }
-                }
-            }
+        if let Some(bounds) = inner.to_member("bounds")?.get()
+            && bounds.to_array()?.next().is_some()
+        {
+            write!(self.writer, ": ")?;
+            crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+                .strict(self.strict)
+                .format_bounds(bounds)?;
         }
 
-        // Format where clauses
-        if let Some(g) = generics.get() {
-            self.format_where_clauses(g)?;
+        let mut has_where_clause = false;
+        if let Some(g) = generics {
+            has_where_clause = self.generics_formatter().format_where_clause(g)?;
+        }
+        if has_where_clause {
+            writeln!(self.writer, ",\n{{")?;
+        } else {
+            writeln!(self.writer, " {{")?;
         }
-
-        writeln!(self.writer, " {{")?;
 
         // Format trait items (methods, associated types, etc.)
         let items = inner.to_member("items")?;
@@ -91,24 +153,15 @@ impl<'a, W: std::io::Write> TraitFormatter<'a, W> {
                     writeln!(self.writer)?;
                 }
 
-                let item_value = self.doc.items.get(&self.doc.json, *item_id)?;
+                let item_value = self.doc.items.get(self.doc.json(), *item_id)?;
                 let item = crate::doc::Item::try_from(item_value)?;
-                let item_name = item.name.as_deref().unwrap_or("?");
-                let item_inner = item.inner(&self.doc.json);
-
-                // Determine item type by checking for function signature
-                if item_inner.to_member("sig")?.get().is_some() {
-                    // It's a function/method
-                    let formatted = crate::format_item::format_function_to_string(
-                        self.doc, item_name, item_inner,
-                    )?;
-                    writeln!(self.writer, "    {};", formatted)?;
-                } else if item_inner.to_member("assoc_type")?.get().is_some() {
-                    // It's an associated type
-                    writeln!(self.writer, "    type {};", item_name)?;
-                } else if item_inner.to_member("assoc_const")?.get().is_some() {
-                    // It's an associated constant
-                    writeln!(self.writer, "    const {};", item_name)?;
+
+                // Members are indented as a whole, including their where clauses
+                let mut member =
+                    TraitFormatter::new(Vec::new(), self.doc, self.name).strict(self.strict);
+                member.format_member(&item)?;
+                for line in String::from_utf8(member.writer).expect("bug").lines() {
+                    writeln!(self.writer, "    {line}")?;
                 }
             }
         }
@@ -117,193 +170,136 @@ impl<'a, W: std::io::Write> TraitFormatter<'a, W> {
         Ok(())
     }
 
-    fn format_generics(&mut self, generics: nojson::RawJsonValue) -> crate::Result<()> {
-        let params = generics.to_member("params")?;
-
-        if let Some(params_array) = params.get() {
-            let params_list: Vec<_> = params_array.to_array()?.collect();
-
-            if !params_list.is_empty() {
-                write!(self.writer, "<")?;
-
-                for (i, param) in params_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    let param_name = param
-                        .to_member("name")?
-                        .required()?
-                        .to_unquoted_string_str()?;
-                    write!(self.writer, "{}", param_name)?;
-
-                    // Format bounds if present
-                    let kind = param.to_member("kind")?;
-                    if let Some(kind_obj) = kind.get()
-                        && let Some(type_bounds) = kind_obj.to_member("type")?.get()
-                    {
-                        self.format_type_bounds(type_bounds)?;
-                    }
-                }
-
-                write!(self.writer, ">")?;
+    /// Writes a trait member without indentation
+    fn format_member(&mut self, item: &crate::doc::Item) -> crate::Result<()> {
+        let item_name = item.name.as_deref().unwrap_or("?");
+        let item_inner = item.inner(self.doc.json());
+
+        match item.kind {
+            crate::doc::ItemKind::Function => {
+                let formatted = crate::format_item::format_function_to_string(
+                    self.doc,
+                    item_name,
+                    item_inner,
+                    self.strict,
+                )?;
+                write!(self.writer, "{}", formatted)?;
+                self.format_method_end(item_inner)?;
             }
+            crate::doc::ItemKind::AssocType => self.format_assoc_type(item_name, item_inner)?,
+            crate::doc::ItemKind::AssocConst => self.format_assoc_const(item_name, item_inner)?,
+            _ => {}
         }
-
         Ok(())
     }
 
-    fn format_type_bounds(&mut self, type_obj: nojson::RawJsonValue) -> crate::Result<()> {
-        let bounds = type_obj.to_member("bounds")?;
-
-        if let Some(bounds_array) = bounds.get() {
-            let bounds_list: Vec<_> = bounds_array.to_array()?.collect();
-
-            if !bounds_list.is_empty() {
-                write!(self.writer, ": ")?;
-
-                for (i, bound) in bounds_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, " + ")?;
-                    }
-
-                    if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
-                        let trait_info = trait_bound.to_member("trait")?.required()?;
-                        let trait_path = trait_info
-                            .to_member("path")?
-                            .required()?
-                            .to_unquoted_string_str()?;
-                        write!(self.writer, "{}", trait_path)?;
-
-                        // Format trait generic args if present
-                        if let Some(args) = trait_info.to_member("args")?.get()
-                            && !args.kind().is_null()
-                        {
-                            self.format_trait_args(args)?;
-                        }
-                    }
-                }
-            }
+    /// Ends a method declaration (provided methods, i.e., those with a default body, get `{ ... }`)
+    fn format_method_end(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
+        let has_body: bool = inner.to_member("has_body")?.required()?.try_into()?;
+        if !has_body {
+            writeln!(self.writer, ";")?;
+            return Ok(());
         }
 
+        let has_where_clause = inner
+            .to_member("generics")?
+            .required()?
+            .to_member("where_predicates")?
+            .required()?
+            .to_array()?
+            .next()
+            .is_some();
+        if has_where_clause {
+            writeln!(self.writer, ",\n{{ ... }}")?;
+        } else {
+            writeln!(self.writer, " {{ ... }}")?;
+        }
         Ok(())
     }
 
-    fn format_trait_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
-        if let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() {
-            write!(self.writer, "<")?;
-
-            let args_array = angle_bracketed.to_member("args")?;
-            if let Some(args_list) = args_array.get() {
-                for (i, arg) in args_list.to_array()?.enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    if let Some(arg_type) = arg.to_member("type")?.get() {
-                        let formatted = crate::format_type::format_to_string(self.doc, arg_type)?;
-                        write!(self.writer, "{}", formatted)?;
-                    }
-                }
-            }
-
-            write!(self.writer, ">")?;
+    /// Writes `type Name<'a>: Bounds = Default where ...;`
+    fn format_assoc_type(
+        &mut self,
+        name: &str,
+        assoc_type: nojson::RawJsonValue,
+    ) -> crate::Result<()> {
+        write!(self.writer, "type {}", name)?;
+        let generics = assoc_type.to_member("generics")?.required()?;
+        self.generics_formatter().format_params(generics)?;
+
+        let bounds = assoc_type.to_member("bounds")?.required()?;
+        if bounds.to_array()?.next().is_some() {
+            write!(self.writer, ": ")?;
+            crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+                .strict(self.strict)
+                .format_bounds(bounds)?;
+        }
+        if let Some(default) = assoc_type.to_member("type")?.get()
+            && !default.kind().is_null()
+        {
+            write!(self.writer, " = ")?;
+            crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+                .strict(self.strict)
+                .format(default)?;
         }
 
+        self.generics_formatter().format_where_clause(generics)?;
+        writeln!(self.writer, ";")?;
         Ok(())
     }
 
-    fn format_where_clauses(&mut self, generics: nojson::RawJsonValue) -> crate::Result<()> {
-        let where_predicates = generics.to_member("where_predicates")?;
-
-        if let Some(predicates) = where_predicates.get() {
-            let predicates_list: Vec<_> = predicates.to_array()?.collect();
-
-            if !predicates_list.is_empty() {
-                write!(self.writer, "\nwhere\n    ")?;
-
-                for (i, predicate) in predicates_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ",\n    ")?;
-                    }
-
-                    self.format_where_predicate(*predicate)?;
-                }
-
-                write!(self.writer, " ")?;
-            }
+    /// Writes `const NAME: Type = default;`
+    fn format_assoc_const(
+        &mut self,
+        name: &str,
+        assoc_const: nojson::RawJsonValue,
+    ) -> crate::Result<()> {
+        write!(self.writer, "const {}: ", name)?;
+        crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
+            .format(assoc_const.to_member("type")?.required()?)?;
+        if let Some(value) = assoc_const.to_member("value")?.get()
+            && !value.kind().is_null()
+        {
+            write!(self.writer, " = {}", value.to_unquoted_string_str()?)?;
         }
-
+        writeln!(self.writer, ";")?;
         Ok(())
     }
 
-    fn format_where_predicate(&mut self, predicate: nojson::RawJsonValue) -> crate::Result<()> {
-        if let Some(bound_predicate) = predicate.to_member("bound_predicate")?.get() {
-            if let Some(lhs) = bound_predicate.to_member("type")?.get() {
-                let formatted_lhs = crate::format_type::format_to_string(self.doc, lhs)?;
-                write!(self.writer, "{}", formatted_lhs)?;
-            }
-
-            let bounds = bound_predicate.to_member("bounds")?;
-            if let Some(bounds_array) = bounds.get() {
-                let bounds_list: Vec<_> = bounds_array.to_array()?.collect();
-
-                if !bounds_list.is_empty() {
-                    write!(self.writer, ": ")?;
-
-                    for (i, bound) in bounds_list.iter().enumerate() {
-                        if i > 0 {
-                            write!(self.writer, " + ")?;
-                        }
-
-                        if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
-                            let trait_info = trait_bound.to_member("trait")?.required()?;
-                            let trait_path = trait_info
-                                .to_member("path")?
-                                .required()?
-                                .to_unquoted_string_str()?;
-                            write!(self.writer, "{}", trait_path)?;
-
-                            if let Some(args) = trait_info.to_member("args")?.get()
-                                && !args.kind().is_null()
-                            {
-                                self.format_trait_args(args)?;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
+    fn generics_formatter(&mut self) -> crate::format_generics::GenericsFormatter<'a, &mut W> {
+        crate::format_generics::GenericsFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
     }
 }
 
 pub fn format_union_to_string(
     doc: &crate::doc::CrateDoc,
     item: &crate::doc::Item,
+    strict: bool,
 ) -> crate::Result<String> {
     let name = item.name.as_ref().expect("bug");
-    let inner = item.inner(&doc.json);
+    let inner = item.inner(doc.json());
     let mut buffer = Vec::new();
-    let mut formatter = StructFormatter::new(&mut buffer, doc, name, "union");
+    let mut formatter = StructFormatter::new(&mut buffer, doc, name, "union").strict(strict);
     formatter
         .format(inner)
-        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json.text()))?;
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
 
 pub fn format_struct_to_string(
     doc: &crate::doc::CrateDoc,
     item: &crate::doc::Item,
+    strict: bool,
 ) -> crate::Result<String> {
     let name = item.name.as_ref().expect("bug");
-    let inner = item.inner(&doc.json);
+    let inner = item.inner(doc.json());
     let mut buffer = Vec::new();
-    let mut formatter = StructFormatter::new(&mut buffer, doc, name, "struct");
+    let mut formatter = StructFormatter::new(&mut buffer, doc, name, "struct").strict(strict);
     formatter
         .format(inner)
-        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json.text()))?;
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
 
@@ -311,6 +307,7 @@ pub fn format_struct_to_string(
 pub struct StructFormatter<'a, W> {
     writer: W,
     doc: &'a crate::doc::CrateDoc,
+    strict: bool,
     name: &'a str,
     kind: &'a str,
 }
@@ -320,53 +317,49 @@ impl<'a, W: std::io::Write> StructFormatter<'a, W> {
         Self {
             writer,
             doc,
+            strict: false,
             name,
             kind,
         }
     }
 
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
         write!(self.writer, "{} {}", self.kind, self.name)?;
+        let generics = inner.to_member("generics")?.get();
+        if let Some(g) = generics {
+            self.generics_formatter().format_params(g)?;
+        }
+
+        // Unions have no `kind` and list their fields directly
+        if let Some(fields) = inner.to_member("fields")?.get() {
+            let has_stripped: bool = inner
+                .to_member("has_stripped_fields")?
+                .required()?
+                .try_into()?;
+            self.format_block_start(generics)?;
+            return self.format_named_fields(fields, has_stripped);
+        }
+
         let kind = inner.to_member("kind")?;
         if let Some(kind_obj) = kind.get() {
             // Check for plain struct variant
             if kind_obj.kind().is_string() && kind_obj.to_unquoted_string_str()? == "unit" {
                 // Unit struct - no fields
+                self.format_where_clause(generics)?;
                 write!(self.writer, ";")?;
             } else if let Some(plain_kind) = kind_obj.to_member("plain")?.get() {
-                writeln!(self.writer, " {{")?;
                 let fields = plain_kind.to_member("fields")?.required()?;
                 let has_stripped: bool = plain_kind
                     .to_member("has_stripped_fields")?
                     .required()?
                     .try_into()?;
-
-                let field_ids: Vec<_> = fields.to_array()?.collect();
-
-                for (i, field_id) in field_ids.iter().enumerate() {
-                    if i > 0 {
-                        writeln!(self.writer, ",")?;
-                    }
-
-                    let field_item_value = self.doc.items.get(&self.doc.json, *field_id)?;
-                    let field_item = crate::doc::Item::try_from(field_item_value)?;
-                    let field_name = field_item.name.as_deref().unwrap_or("?");
-                    let field_inner = field_item.inner(&self.doc.json);
-                    let formatted_type =
-                        crate::format_type::format_to_string(self.doc, field_inner)?;
-
-                    write!(self.writer, "    {}: {}", field_name, formatted_type)?;
-                }
-
-                if !field_ids.is_empty() {
-                    writeln!(self.writer, ",")?;
-                }
-
-                if has_stripped {
-                    writeln!(self.writer, "    // ... other fields")?;
-                }
-
-                write!(self.writer, "}}")?;
+                self.format_block_start(generics)?;
+                self.format_named_fields(fields, has_stripped)?;
             } else if let Some(tuple_kind) = kind_obj.to_member("tuple")?.get() {
                 // Tuple struct - tuple_kind is an array
                 let mut field_ids: Vec<_> = tuple_kind.to_array()?.collect();
@@ -380,11 +373,11 @@ impl<'a, W: std::io::Write> StructFormatter<'a, W> {
                         write!(self.writer, ", ")?;
                     }
 
-                    let field_item_value = self.doc.items.get(&self.doc.json, *field_id)?;
+                    let field_item_value = self.doc.items.get(self.doc.json(), *field_id)?;
                     let field_item = crate::doc::Item::try_from(field_item_value)?;
-                    let field_inner = field_item.inner(&self.doc.json);
+                    let field_inner = field_item.inner(self.doc.json());
                     let formatted_type =
-                        crate::format_type::format_to_string(self.doc, field_inner)?;
+                        crate::format_type::format_to_string(self.doc, field_inner, self.strict)?;
 
                     write!(self.writer, "{}", formatted_type)?;
                 }
@@ -396,25 +389,87 @@ impl<'a, W: std::io::Write> StructFormatter<'a, W> {
                     write!(self.writer, "/* ... */")?;
                 }
 
-                write!(self.writer, ");")?;
+                write!(self.writer, ")")?;
+                self.format_where_clause(generics)?;
+                write!(self.writer, ";")?;
             }
         }
 
         Ok(())
     }
+
+    fn format_named_fields(
+        &mut self,
+        fields: nojson::RawJsonValue,
+        has_stripped: bool,
+    ) -> crate::Result<()> {
+        let field_ids: Vec<_> = fields.to_array()?.collect();
+
+        for (i, field_id) in field_ids.iter().enumerate() {
+            if i > 0 {
+                writeln!(self.writer, ",")?;
+            }
+
+            let field_item_value = self.doc.items.get(self.doc.json(), *field_id)?;
+            let field_item = crate::doc::Item::try_from(field_item_value)?;
+            let field_name = field_item.name.as_deref().unwrap_or("?");
+            let field_inner = field_item.inner(self.doc.json());
+            let formatted_type =
+                crate::format_type::format_to_string(self.doc, field_inner, self.strict)?;
+
+            write!(self.writer, "    {}: {}", field_name, formatted_type)?;
+        }
+
+        if !field_ids.is_empty() {
+            writeln!(self.writer, ",")?;
+        }
+
+        if has_stripped {
+            writeln!(self.writer, "    // ... other fields")?;
+        }
+
+        write!(self.writer, "}}")?;
+        Ok(())
+    }
+
+    /// Writes the where clause (if any) followed by ` {` or `,\n{` and a newline
+    fn format_block_start(&mut self, generics: Option<nojson::RawJsonValue>) -> crate::Result<()> {
+        if self.format_where_clause(generics)? {
+            writeln!(self.writer, ",\n{{")?;
+        } else {
+            writeln!(self.writer, " {{")?;
+        }
+        Ok(())
+    }
+
+    fn format_where_clause(
+        &mut self,
+        generics: Option<nojson::RawJsonValue>,
+    ) -> crate::Result<bool> {
+        match generics {
+            Some(g) => self.generics_formatter().format_where_clause(g),
+            None => Ok(false),
+        }
+    }
+
+    fn generics_formatter(&mut self) -> crate::format_generics::GenericsFormatter<'a, &mut W> {
+        crate::format_generics::GenericsFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
+    }
 }
 
 pub fn format_enum_to_string(
     doc: &crate::doc::CrateDoc,
     item: &crate::doc::Item,
+    strict: bool,
 ) -> crate::Result<String> {
     let name = item.name.as_ref().expect("bug");
-    let inner = item.inner(&doc.json);
+    let inner = item.inner(doc.json());
     let mut buffer = Vec::new();
-    let mut formatter = EnumFormatter::new(&mut buffer, doc, name);
+    let mut formatter = EnumFormatter::new(&mut buffer, doc, name).strict(strict);
     formatter
         .format(inner)
-        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json.text()))?;
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
 
@@ -422,16 +477,37 @@ pub fn format_enum_to_string(
 pub struct EnumFormatter<'a, W> {
     writer: W,
     doc: &'a crate::doc::CrateDoc,
+    strict: bool,
     name: &'a str,
 }
 
 impl<'a, W: std::io::Write> EnumFormatter<'a, W> {
     pub fn new(writer: W, doc: &'a crate::doc::CrateDoc, name: &'a str) -> Self {
-        Self { writer, doc, name }
+        Self {
+            writer,
+            doc,
+            strict: false,
+            name,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
-        writeln!(self.writer, "enum {} {{", self.name)?;
+        write!(self.writer, "enum {}", self.name)?;
+        let mut has_where_clause = false;
+        if let Some(generics) = inner.to_member("generics")?.get() {
+            self.generics_formatter().format_params(generics)?;
+            has_where_clause = self.generics_formatter().format_where_clause(generics)?;
+        }
+        if has_where_clause {
+            writeln!(self.writer, ",\n{{")?;
+        } else {
+            writeln!(self.writer, " {{")?;
+        }
 
         let variants = inner.to_member("variants")?;
         if let Some(variants_array) = variants.get() {
@@ -442,10 +518,10 @@ impl<'a, W: std::io::Write> EnumFormatter<'a, W> {
                     writeln!(self.writer, ",")?;
                 }
 
-                let variant_item_value = self.doc.items.get(&self.doc.json, *variant_id)?;
+                let variant_item_value = self.doc.items.get(self.doc.json(), *variant_id)?;
                 let variant_item = crate::doc::Item::try_from(variant_item_value)?;
                 let variant_name = variant_item.name.as_deref().unwrap_or("?");
-                let variant_inner = variant_item.inner(&self.doc.json);
+                let variant_inner = variant_item.inner(self.doc.json());
 
                 write!(self.writer, "    {}", variant_name)?;
 
@@ -523,19 +599,25 @@ impl<'a, W: std::io::Write> EnumFormatter<'a, W> {
         write!(self.writer, "\n}}")?;
         Ok(())
     }
+
+    fn generics_formatter(&mut self) -> crate::format_generics::GenericsFormatter<'a, &mut W> {
+        crate::format_generics::GenericsFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
+    }
 }
 
 pub fn format_enum_variant_to_string(
     doc: &crate::doc::CrateDoc,
     item: &crate::doc::Item,
+    strict: bool,
 ) -> crate::Result<String> {
     let name = item.name.as_ref().expect("bug");
-    let inner = item.inner(&doc.json);
+    let inner = item.inner(doc.json());
     let mut buffer = Vec::new();
-    let mut formatter = EnumVariantFormatter::new(&mut buffer, doc, name);
+    let mut formatter = EnumVariantFormatter::new(&mut buffer, doc, name).strict(strict);
     formatter
         .format(inner)
-        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json.text()))?;
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
 
@@ -543,12 +625,23 @@ pub fn format_enum_variant_to_string(
 pub struct EnumVariantFormatter<'a, W> {
     writer: W,
     doc: &'a crate::doc::CrateDoc,
+    strict: bool,
     name: &'a str,
 }
 
 impl<'a, W: std::io::Write> EnumVariantFormatter<'a, W> {
     pub fn new(writer: W, doc: &'a crate::doc::CrateDoc, name: &'a str) -> Self {
-        Self { writer, doc, name }
+        Self {
+            writer,
+            doc,
+            strict: false,
+            name,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
@@ -606,11 +699,12 @@ impl<'a, W: std::io::Write> EnumVariantFormatter<'a, W> {
                 write!(self.writer, ", ")?;
             }
 
-            let field_item_value = self.doc.items.get(&self.doc.json, *field_id_value)?;
+            let field_item_value = self.doc.items.get(self.doc.json(), *field_id_value)?;
             let field_item = crate::doc::Item::try_from(field_item_value)?;
             let field_name = field_item.name.as_deref().unwrap_or("?");
-            let field_inner = field_item.inner(&self.doc.json);
-            let formatted_type = crate::format_type::format_to_string(self.doc, field_inner)?;
+            let field_inner = field_item.inner(self.doc.json());
+            let formatted_type =
+                crate::format_type::format_to_string(self.doc, field_inner, self.strict)?;
 
             write!(self.writer, "{}: {}", field_name, formatted_type)?;
         }
@@ -642,10 +736,11 @@ impl<'a, W: std::io::Write> EnumVariantFormatter<'a, W> {
                 write!(self.writer, ", ")?;
             }
 
-            let field_item_value = self.doc.items.get(&self.doc.json, *field_id_value)?;
+            let field_item_value = self.doc.items.get(self.doc.json(), *field_id_value)?;
             let field_item = crate::doc::Item::try_from(field_item_value)?;
-            let field_inner = field_item.inner(&self.doc.json);
-            let formatted_type = crate::format_type::format_to_string(self.doc, field_inner)?;
+            let field_inner = field_item.inner(self.doc.json());
+            let formatted_type =
+                crate::format_type::format_to_string(self.doc, field_inner, self.strict)?;
 
             write!(self.writer, "{}", formatted_type)?;
         }
@@ -662,13 +757,226 @@ impl<'a, W: std::io::Write> EnumVariantFormatter<'a, W> {
     }
 }
 
+pub fn format_impl_to_string(
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+    strict: bool,
+) -> crate::Result<String> {
+    let inner = item.inner(doc.json());
+    let mut buffer = Vec::new();
+    let mut formatter = ImplFormatter::new(&mut buffer, doc).strict(strict);
+    formatter
+        .format(inner)
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
+    Ok(String::from_utf8(buffer).expect("bug"))
+}
+
+pub fn format_impl_header_to_string(
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+    strict: bool,
+) -> crate::Result<String> {
+    let inner = item.inner(doc.json());
+    let mut buffer = Vec::new();
+    let mut formatter = ImplFormatter::new(&mut buffer, doc).strict(strict);
+    formatter
+        .format_header(inner)
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
+    Ok(String::from_utf8(buffer).expect("bug"))
+}
+
+#[derive(Debug)]
+pub struct ImplFormatter<'a, W> {
+    writer: W,
+    doc: &'a crate::doc::CrateDoc,
+    strict: bool,
+}
+
+impl<'a, W: std::io::Write> ImplFormatter<'a, W> {
+    pub fn new(writer: W, doc: &'a crate::doc::CrateDoc) -> Self {
+        Self {
+            writer,
+            doc,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
+        let has_where_clauses = self.format_header(inner)?;
+        if has_where_clauses {
+            write!(self.writer, "\n{{")?;
+        } else {
+            write!(self.writer, " {{")?;
+        }
+
+        // Inherent impls may contain private items, while trait impl items are always public
+        let is_inherent = inner.to_member("trait")?.required()?.kind().is_null();
+
+        let mut formatted_items = Vec::new();
+        for item_id in inner.to_member("items")?.required()?.to_array()? {
+            let item_value = self.doc.items.get(self.doc.json(), item_id)?;
+            let item = crate::doc::Item::try_from(item_value)?;
+            if is_inherent && !item.is_public {
+                continue;
+            }
+            if let Some(formatted) = self.format_impl_item(&item)? {
+                formatted_items.push(formatted);
+            }
+        }
+
+        if !formatted_items.is_empty() {
+            writeln!(self.writer)?;
+            for formatted in formatted_items {
+                for line in formatted.lines() {
+                    writeln!(self.writer, "    {}", line)?;
+                }
+            }
+        }
+
+        write!(self.writer, "}}")?;
+        Ok(())
+    }
+
+    /// Writes `impl<..> Trait for Type where ..` and returns whether a where clause was written.
+    pub fn format_header(&mut self, inner: nojson::RawJsonValue) -> crate::Result<bool> {
+        let is_unsafe: bool = inner.to_member("is_unsafe")?.required()?.try_into()?;
+        let is_negative: bool = inner.to_member("is_negative")?.required()?.try_into()?;
+
+        if is_unsafe {
+            write!(self.writer, "unsafe ")?;
+        }
+        write!(self.writer, "impl")?;
+
+        // Format generics
+        let generics = inner.to_member("generics")?;
+        if let Some(g) = generics.get() {
+            self.generics_formatter().format_params(g)?;
+        }
+        write!(self.writer, " ")?;
+
+        // Format trait (if any)
+        let trait_info = inner.to_member("trait")?.required()?;
+        if !trait_info.kind().is_null() {
+            if is_negative {
+                write!(self.writer, "!")?;
+            }
+            crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+                .strict(self.strict)
+                .format_path(trait_info)?;
+            write!(self.writer, " for ")?;
+        }
+
+        // Format implementing type
+        let for_type = inner.to_member("for")?.required()?;
+        let formatted = crate::format_type::format_to_string(self.doc, for_type, self.strict)?;
+        write!(self.writer, "{}", formatted)?;
+
+        // Format where clauses
+        if let Some(g) = generics.get() {
+            let has_where_clause = self.generics_formatter().format_where_clause(g)?;
+            if has_where_clause {
+                write!(self.writer, ",")?;
+            }
+            return Ok(has_where_clause);
+        }
+        Ok(false)
+    }
+
+    fn format_impl_item(&mut self, item: &crate::doc::Item) -> crate::Result<Option<String>> {
+        let name = item.name.as_deref().unwrap_or("?");
+        let item_inner = item.inner(self.doc.json());
+        let formatted = match item.kind {
+            crate::doc::ItemKind::Function => {
+                let formatted = crate::format_item::format_function_to_string(
+                    self.doc,
+                    name,
+                    item_inner,
+                    self.strict,
+                )?;
+                format!("{};", formatted)
+            }
+            crate::doc::ItemKind::AssocType => {
+                let view = crate::item_view::TypeView::new(self.doc, item).strict(self.strict);
+                if let Some(ty) = view.ty()? {
+                    format!("type {} = {};", view.name()?, ty)
+                } else {
+                    format!("type {};", view.name()?)
+                }
+            }
+            crate::doc::ItemKind::AssocConst => {
+                let view = crate::item_view::ConstantView::new(self.doc, item).strict(self.strict);
+                format!("const {}: {};", view.name(), view.ty()?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(formatted))
+    }
+
+    fn generics_formatter(&mut self) -> crate::format_generics::GenericsFormatter<'a, &mut W> {
+        crate::format_generics::GenericsFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
+    }
+}
+
+pub fn format_use_to_string(
+    doc: &crate::doc::CrateDoc,
+    item: &crate::doc::Item,
+) -> crate::Result<String> {
+    let inner = item.inner(doc.json());
+    let mut buffer = Vec::new();
+    let mut formatter = UseFormatter::new(&mut buffer);
+    formatter
+        .format(inner)
+        .map_err(|e| e.set_json_span(inner).set_json_text(doc.json().text()))?;
+    Ok(String::from_utf8(buffer).expect("bug"))
+}
+
+#[derive(Debug)]
+pub struct UseFormatter<W> {
+    writer: W,
+}
+
+impl<W: std::io::Write> UseFormatter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
+        let source = inner
+            .to_member("source")?
+            .required()?
+            .to_unquoted_string_str()?;
+        let name = inner
+            .to_member("name")?
+            .required()?
+            .to_unquoted_string_str()?;
+        let is_glob: bool = inner.to_member("is_glob")?.required()?.try_into()?;
+
+        write!(self.writer, "pub use {}", source)?;
+        if is_glob {
+            write!(self.writer, "::*")?;
+        } else if source.rsplit("::").next() != Some(name.as_ref()) {
+            write!(self.writer, " as {}", name)?;
+        }
+        write!(self.writer, ";")?;
+
+        Ok(())
+    }
+}
+
 pub fn format_function_to_string(
     doc: &crate::doc::CrateDoc,
     name: &str,
     inner: nojson::RawJsonValue,
+    strict: bool,
 ) -> crate::Result<String> {
     let mut buffer = Vec::new();
-    let mut formatter = FunctionFormatter::new(&mut buffer, doc, name);
+    let mut formatter = FunctionFormatter::new(&mut buffer, doc, name).strict(strict);
     formatter.format(inner)?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
@@ -677,12 +985,23 @@ pub fn format_function_to_string(
 pub struct FunctionFormatter<'a, W> {
     writer: W,
     doc: &'a crate::doc::CrateDoc,
+    strict: bool,
     name: &'a str,
 }
 
 impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
     pub fn new(writer: W, doc: &'a crate::doc::CrateDoc, name: &'a str) -> Self {
-        Self { writer, doc, name }
+        Self {
+            writer,
+            doc,
+            strict: false,
+            name,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn format(&mut self, inner: nojson::RawJsonValue) -> crate::Result<()> {
@@ -694,7 +1013,7 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
     fn format_function_signature(&mut self, function: nojson::RawJsonValue) -> crate::Result<()> {
         let sig = function.to_member("sig")?.required()?;
 
-        // Format header (const, unsafe, async)
+        // Format header (const, async, unsafe, extern)
         self.format_function_header(function)?;
 
         // Format function name
@@ -703,7 +1022,7 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
         // Format generics
         let generics = function.to_member("generics")?;
         if let Some(g) = generics.get() {
-            self.format_generics(g)?;
+            self.generics_formatter().format_params(g)?;
         }
 
         // Format parameters
@@ -716,7 +1035,7 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
 
         // Format where clauses
         if let Some(g) = generics.get() {
-            self.format_where_clauses(g)?;
+            self.generics_formatter().format_where_clause(g)?;
         }
 
         Ok(())
@@ -733,108 +1052,15 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
             if is_const {
                 write!(self.writer, "const ")?;
             }
-            if is_unsafe {
-                write!(self.writer, "unsafe ")?;
-            }
             if is_async {
                 write!(self.writer, "async ")?;
             }
-        }
-
-        Ok(())
-    }
-
-    fn format_generics(&mut self, generics: nojson::RawJsonValue) -> crate::Result<()> {
-        let params = generics.to_member("params")?;
-
-        if let Some(params_array) = params.get() {
-            let params_list: Vec<_> = params_array.to_array()?.collect();
-
-            if !params_list.is_empty() {
-                write!(self.writer, "<")?;
-
-                for (i, param) in params_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    let param_name = param
-                        .to_member("name")?
-                        .required()?
-                        .to_unquoted_string_str()?;
-                    write!(self.writer, "{}", param_name)?;
-
-                    // Format bounds if present
-                    let kind = param.to_member("kind")?;
-                    if let Some(kind_obj) = kind.get()
-                        && let Some(type_bounds) = kind_obj.to_member("type")?.get()
-                    {
-                        self.format_type_bounds(type_bounds)?;
-                    }
-                }
-
-                write!(self.writer, ">")?;
-            }
-        }
-
-        Ok(())
-    }
-
-    fn format_type_bounds(&mut self, type_obj: nojson::RawJsonValue) -> crate::Result<()> {
-        let bounds = type_obj.to_member("bounds")?;
-
-        if let Some(bounds_array) = bounds.get() {
-            let bounds_list: Vec<_> = bounds_array.to_array()?.collect();
-
-            if !bounds_list.is_empty() {
-                write!(self.writer, ": ")?;
-
-                for (i, bound) in bounds_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, " + ")?;
-                    }
-
-                    if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
-                        let trait_info = trait_bound.to_member("trait")?.required()?;
-                        let trait_path = trait_info
-                            .to_member("path")?
-                            .required()?
-                            .to_unquoted_string_str()?;
-                        write!(self.writer, "{}", trait_path)?;
-
-                        // Format trait generic args if present
-                        if let Some(args) = trait_info.to_member("args")?.get()
-                            && !args.kind().is_null()
-                        {
-                            self.format_trait_args(args)?;
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
-
-    fn format_trait_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
-        if let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() {
-            write!(self.writer, "<")?;
-
-            let args_array = angle_bracketed.to_member("args")?;
-            if let Some(args_list) = args_array.get() {
-                for (i, arg) in args_list.to_array()?.enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    if let Some(arg_type) = arg.to_member("type")?.get() {
-                        let formatted = crate::format_type::format_to_string(self.doc, arg_type)?;
-                        write!(self.writer, "{}", formatted)?;
-                    }
-                }
+            if is_unsafe {
+                write!(self.writer, "unsafe ")?;
             }
-
-            write!(self.writer, ">")?;
+            crate::format_type::TypeFormatter::new(&mut self.writer, self.doc)
+                .strict(self.strict)
+                .format_abi(header.to_member("abi")?.required()?)?;
         }
 
         Ok(())
@@ -855,15 +1081,63 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
                 let param_name = input_items[0].to_unquoted_string_str()?;
                 let param_type = input_items[1];
 
+                if i == 0 && self.format_receiver(&param_name, param_type)? {
+                    continue;
+                }
                 write!(self.writer, "{}: ", param_name)?;
-                let formatted_type = crate::format_type::format_to_string(self.doc, param_type)?;
+                let formatted_type =
+                    crate::format_type::format_to_string(self.doc, param_type, self.strict)?;
                 write!(self.writer, "{}", formatted_type)?;
             }
         }
 
+        let is_c_variadic: bool = sig.to_member("is_c_variadic")?.required()?.try_into()?;
+        if is_c_variadic {
+            if inputs.to_array()?.next().is_some() {
+                write!(self.writer, ", ")?;
+            }
+            write!(self.writer, "...")?;
+        }
+
         Ok(())
     }
 
+    /// Writes shorthand receivers (`self`, `mut self`, `&self` and `&'a mut self`)
+    ///
+    /// Returns `false` for other parameters, including receivers with explicit types
+    /// such as `self: Box<Self>`.
+    fn format_receiver(&mut self, name: &str, ty: nojson::RawJsonValue) -> crate::Result<bool> {
+        if name.strip_prefix("mut ").unwrap_or(name) != "self" {
+            return Ok(false);
+        }
+        if is_self_type(ty)? {
+            write!(self.writer, "{}", name)?;
+            return Ok(true);
+        }
+
+        let Some(borrowed_ref) = ty.to_member("borrowed_ref")?.get() else {
+            return Ok(false);
+        };
+        if name != "self" || !is_self_type(borrowed_ref.to_member("type")?.required()?)? {
+            return Ok(false);
+        }
+        write!(self.writer, "&")?;
+        if let Some(lifetime) = borrowed_ref.to_member("lifetime")?.get()
+            && !lifetime.kind().is_null()
+        {
+            write!(self.writer, "{} ", lifetime.to_unquoted_string_str()?)?;
+        }
+        let is_mutable: bool = borrowed_ref
+            .to_member("is_mutable")?
+            .required()?
+            .try_into()?;
+        if is_mutable {
+            write!(self.writer, "mut ")?;
+        }
+        write!(self.writer, "self")?;
+        Ok(true)
+    }
+
     fn format_function_output(&mut self, sig: nojson::RawJsonValue) -> crate::Result<()> {
         let output = sig.to_member("output")?;
 
@@ -871,85 +1145,25 @@ impl<'a, W: std::io::Write> FunctionFormatter<'a, W> {
             && !output_type.kind().is_null()
         {
             write!(self.writer, " -> ")?;
-            let formatted_type = crate::format_type::format_to_string(self.doc, output_type)?;
+            let formatted_type =
+                crate::format_type::format_to_string(self.doc, output_type, self.strict)?;
             write!(self.writer, "{}", formatted_type)?;
         }
 
         Ok(())
     }
 
-    fn format_where_clauses(&mut self, generics: nojson::RawJsonValue) -> crate::Result<()> {
-        let where_predicates = generics.to_member("where_predicates")?;
-
-        if let Some(predicates) = where_predicates.get() {
-            let predicates_list: Vec<_> = predicates.to_array()?.collect();
-
-            if !predicates_list.is_empty() {
-                write!(self.writer, "\nwhere\n    ")?;
-
-                for (i, predicate) in predicates_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(self.writer, ",\n    ")?;
-                    }
-
-                    // Format the where predicate
-                    self.format_where_predicate(*predicate)?;
-                }
-            }
-        }
-
-        Ok(())
+    fn generics_formatter(&mut self) -> crate::format_generics::GenericsFormatter<'a, &mut W> {
+        crate::format_generics::GenericsFormatter::new(&mut self.writer, self.doc)
+            .strict(self.strict)
     }
+}
 
-    fn format_where_predicate(&mut self, predicate: nojson::RawJsonValue) -> crate::Result<()> {
-        // Extract bound_predicate wrapper
-        if let Some(bound_predicate) = predicate.to_member("bound_predicate")?.get() {
-            // Extract the type being constrained
-            if let Some(lhs) = bound_predicate.to_member("type")?.get() {
-                let formatted_lhs = crate::format_type::format_to_string(self.doc, lhs)?;
-                write!(self.writer, "{}", formatted_lhs)?;
-            }
-
-            // Format the bounds
-            let bounds = bound_predicate.to_member("bounds")?;
-            if let Some(bounds_array) = bounds.get() {
-                let bounds_list: Vec<_> = bounds_array.to_array()?.collect();
-
-                if !bounds_list.is_empty() {
-                    write!(self.writer, ": ")?;
-
-                    for (i, bound) in bounds_list.iter().enumerate() {
-                        if i > 0 {
-                            write!(self.writer, " + ")?;
-                        }
-
-                        if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
-                            let trait_info = trait_bound.to_member("trait")?.required()?;
-                            let trait_path = trait_info
-                                .to_member("path")?
-                                .required()?
-                                .to_unquoted_string_str()?;
-
-                            if trait_path.is_empty() {
-                                // Handle empty path (associated types)
-                                write!(self.writer, "?")?;
-                            } else {
-                                write!(self.writer, "{}", trait_path)?;
-                            }
-
-                            if let Some(args) = trait_info.to_member("args")?.get()
-                                && !args.kind().is_null()
-                            {
-                                self.format_trait_args(args)?;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
+fn is_self_type(ty: nojson::RawJsonValue) -> crate::Result<bool> {
+    Ok(match ty.to_member("generic")?.get() {
+        Some(generic) => generic.to_unquoted_string_str()? == "Self",
+        None => false,
+    })
 }
 
 #[cfg(test)]
@@ -1035,7 +1249,7 @@ mod tests {
             }"#;
 
         let raw_json = nojson::RawJson::parse(json_str)?;
-        let formatted = format_function_to_string(&doc, "var", raw_json.value())?;
+        let formatted = format_function_to_string(&doc, "var", raw_json.value(), false)?;
 
         assert_eq!(
             formatted,
@@ -1169,10 +1383,10 @@ mod tests {
     }"#;
 
         let raw_json = nojson::RawJson::parse(json_str)?;
-        let formatted = format_function_to_string(&doc, "test_fn", raw_json.value())?;
+        let formatted = format_function_to_string(&doc, "test_fn", raw_json.value(), false)?;
         assert_eq!(
             formatted,
-            "fn test_fn<B>(self: &mut Self) -> Option\nwhere\n    Self: Sized,\n    B: Default"
+            "fn test_fn<B>(&mut self) -> Option\nwhere\n    Self: Sized,\n    B: Default"
         );
         Ok(())
     }
@@ -1195,7 +1409,7 @@ mod tests {
     }"#;
 
         let raw_json = nojson::RawJson::parse(json_str)?;
-        let formatted = format_function_to_string(&doc, "new", raw_json.value())?;
+        let formatted = format_function_to_string(&doc, "new", raw_json.value(), false)?;
 
         assert_eq!(
             formatted,
@@ -1278,11 +1492,11 @@ mod tests {
     }"#;
 
         let raw_json = nojson::RawJson::parse(json_str)?;
-        let formatted = format_function_to_string(&doc, "into_iter", raw_json.value())?;
+        let formatted = format_function_to_string(&doc, "into_iter", raw_json.value(), false)?;
 
         assert_eq!(
             formatted,
-            "fn into_iter(self: Self) -> Result<impl Iterator<Item = Self>, JsonParseError>"
+            "fn into_iter(self) -> Result<impl Iterator<Item = Self>, JsonParseError>"
         );
 
         Ok(())
@@ -1294,15 +1508,208 @@ mod tests {
         let json_str = r#"{"sig":{"inputs":[["name",{"borrowed_ref":{"lifetime":"'static","is_mutable":false,"type":{"primitive":"str"}}}]],"output":{"resolved_path":{"path":"OptSpec","id":411,"args":null}},"is_c_variadic":false},"generics":{"params":[],"where_predicates":[]},"header":{"is_const":true,"is_unsafe":false,"is_async":false,"abi":"Rust"},"has_body":true}"#;
 
         let raw_json = nojson::RawJson::parse(json_str)?;
-        let formatted = format_function_to_string(&doc, "new_opt", raw_json.value())?;
+        let formatted = format_function_to_string(&doc, "new_opt", raw_json.value(), false)?;
 
         assert_eq!(formatted, "const fn new_opt(name: &'static str) -> OptSpec");
 
         Ok(())
     }
 
+    #[test]
+    fn format_function_receivers() -> crate::Result<()> {
+        let doc = empty_doc();
+        let cases = [
+            (r#"["self", {"generic": "Self"}]"#, "self"),
+            (r#"["mut self", {"generic": "Self"}]"#, "mut self"),
+            (
+                r#"["self", {"borrowed_ref": {"lifetime": null, "is_mutable": false, "type": {"generic": "Self"}}}]"#,
+                "&self",
+            ),
+            (
+                r#"["self", {"borrowed_ref": {"lifetime": "'a", "is_mutable": true, "type": {"generic": "Self"}}}]"#,
+                "&'a mut self",
+            ),
+            (
+                r#"["self", {"resolved_path": {"path": "Box", "id": 1, "args": {"angle_bracketed": {"args": [{"type": {"generic": "Self"}}], "constraints": []}}}}]"#,
+                "self: Box<Self>",
+            ),
+            (
+                r#"["self", {"resolved_path": {"path": "Pin", "id": 2, "args": {"angle_bracketed": {"args": [{"type": {"borrowed_ref": {"lifetime": null, "is_mutable": true, "type": {"generic": "Self"}}}}], "constraints": []}}}}]"#,
+                "self: Pin<&mut Self>",
+            ),
+        ];
+        for (input, expected) in cases {
+            let json_str = format!(
+                r#"{{"sig": {{"inputs": [{input}], "output": null, "is_c_variadic": false}}, "generics": {{"params": [], "where_predicates": []}}, "header": {{"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}}, "has_body": true}}"#
+            );
+            let raw_json = nojson::RawJson::parse(&json_str)?;
+            let formatted = format_function_to_string(&doc, "f", raw_json.value(), false)?;
+            assert_eq!(formatted, format!("fn f({expected})"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn format_extern_variadic_function() -> crate::Result<()> {
+        let doc = empty_doc();
+        let json_str = r#"{"sig":{"inputs":[["format",{"raw_pointer":{"is_mutable":false,"type":{"primitive":"u8"}}}]],"output":{"primitive":"i32"},"is_c_variadic":true},"generics":{"params":[],"where_predicates":[]},"header":{"is_const":false,"is_unsafe":true,"is_async":false,"abi":{"C":{"unwind":false}}},"has_body":false}"#;
+
+        let raw_json = nojson::RawJson::parse(json_str)?;
+        let formatted = format_function_to_string(&doc, "printf", raw_json.value(), false)?;
+
+        assert_eq!(
+            formatted,
+            "unsafe extern \"C\" fn printf(format: *const u8, ...) -> i32"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_function_with_impl_trait_argument() -> crate::Result<()> {
+        let doc = empty_doc();
+        let json_str = r#"{"sig":{"inputs":[["value",{"impl_trait":[{"trait_bound":{"trait":{"path":"Display","id":3,"args":null},"generic_params":[],"modifier":"none"}}]}]],"output":null,"is_c_variadic":false},"generics":{"params":[{"name":"impl Display","kind":{"type":{"bounds":[{"trait_bound":{"trait":{"path":"Display","id":3,"args":null},"generic_params":[],"modifier":"none"}}],"default":null,"is_synthetic":true}}}],"where_predicates":[]},"header":{"is_const":false,"is_unsafe":false,"is_async":false,"abi":"Rust"},"has_body":true}"#;
+
+        let raw_json = nojson::RawJson::parse(json_str)?;
+        let formatted = format_function_to_string(&doc, "print", raw_json.value(), false)?;
+
+        assert_eq!(formatted, "fn print(value: impl Display)");
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_impl_header_with_trait_and_where_clause() -> crate::Result<()> {
+        let doc = empty_doc();
+        let json_str = r#"{
+        "is_unsafe": false,
+        "generics": {
+            "params": [
+                {"name": "'a", "kind": {"lifetime": {"outlives": []}}},
+                {"name": "K", "kind": {"type": {"bounds": [], "default": null, "is_synthetic": false}}}
+            ],
+            "where_predicates": [
+                {
+                    "bound_predicate": {
+                        "type": {"generic": "K"},
+                        "bounds": [{"trait_bound": {"trait": {"path": "Eq", "id": 312, "args": null}, "generic_params": [], "modifier": "none"}}],
+                        "generic_params": []
+                    }
+                }
+            ]
+        },
+        "provided_trait_methods": [],
+        "trait": {"path": "From", "id": 205, "args": {"angle_bracketed": {"args": [{"type": {"borrowed_ref": {"lifetime": "'a", "is_mutable": false, "type": {"generic": "K"}}}}], "constraints": []}}},
+        "for": {"resolved_path": {"path": "Key", "id": 10, "args": {"angle_bracketed": {"args": [{"type": {"generic": "K"}}], "constraints": []}}}},
+        "items": [],
+        "is_negative": false,
+        "is_synthetic": false,
+        "blanket_impl": null
+    }"#;
+
+        let raw_json = nojson::RawJson::parse(json_str)?;
+        let mut buffer = Vec::new();
+        let mut formatter = ImplFormatter::new(&mut buffer, &doc);
+        formatter.format(raw_json.value())?;
+
+        let result = String::from_utf8_lossy(&buffer);
+        assert_eq!(
+            result,
+            "impl<'a, K> From<&'a K> for Key<K>\nwhere\n    K: Eq,\n{}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_impl_header_unsafe_negative() -> crate::Result<()> {
+        let doc = empty_doc();
+        let json_str = r#"{"is_unsafe": true, "generics": {"params": [], "where_predicates": []}, "provided_trait_methods": [], "trait": {"path": "Send", "id": 6, "args": null}, "for": {"resolved_path": {"path": "Handle", "id": 10, "args": null}}, "items": [], "is_negative": true, "is_synthetic": false, "blanket_impl": null}"#;
+
+        let raw_json = nojson::RawJson::parse(json_str)?;
+        let mut buffer = Vec::new();
+        let mut formatter = ImplFormatter::new(&mut buffer, &doc);
+        let has_where_clauses = formatter.format_header(raw_json.value())?;
+
+        let result = String::from_utf8_lossy(&buffer);
+        assert_eq!(result, "unsafe impl !Send for Handle");
+        assert!(!has_where_clauses);
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_trait_with_associated_items() -> crate::Result<()> {
+        let text = r#"{"format_version": 57, "root": 0, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1]}}, "docs": null, "deprecation": null},
+            "1": {"id": 1, "name": "Container", "visibility": "public", "inner": {"trait": {"is_auto": false, "is_unsafe": false, "is_dyn_compatible": true, "items": [2, 3, 4, 5, 6], "generics": {"params": [], "where_predicates": []}, "bounds": [], "implementations": []}}, "docs": null, "deprecation": null},
+            "2": {"id": 2, "name": "Item", "visibility": "default", "inner": {"assoc_type": {"generics": {"params": [], "where_predicates": []}, "bounds": [{"trait_bound": {"trait": {"path": "Clone", "id": 9, "args": null}, "generic_params": [], "modifier": "none"}}], "type": {"primitive": "u8"}}}, "docs": null, "deprecation": null},
+            "3": {"id": 3, "name": "Iter", "visibility": "default", "inner": {"assoc_type": {"generics": {"params": [{"name": "'a", "kind": {"lifetime": {"outlives": []}}}], "where_predicates": [{"bound_predicate": {"type": {"generic": "Self"}, "bounds": [{"outlives": "'a"}], "generic_params": []}}]}, "bounds": [], "type": null}}, "docs": null, "deprecation": null},
+            "4": {"id": 4, "name": "N", "visibility": "default", "inner": {"assoc_const": {"type": {"primitive": "usize"}, "value": "3"}}, "docs": null, "deprecation": null},
+            "5": {"id": 5, "name": "len", "visibility": "default", "inner": {"function": {"sig": {"inputs": [], "output": {"primitive": "usize"}, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": false}}, "docs": null, "deprecation": null},
+            "6": {"id": 6, "name": "is_empty", "visibility": "default", "inner": {"function": {"sig": {"inputs": [], "output": {"primitive": "bool"}, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": null, "deprecation": null}
+        }}"#;
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+        let item = doc
+            .items
+            .get_by_id(doc.json(), crate::doc::ItemId(1))
+            .expect("bug");
+        let item = crate::doc::Item::try_from(item)?;
+
+        assert_eq!(
+            format_trait_to_string(&doc, &item, false)?,
+            concat!(
+                "trait Container {\n",
+                "    type Item: Clone = u8;\n",
+                "\n",
+                "    type Iter<'a>\n    where\n        Self: 'a;\n",
+                "\n",
+                "    const N: usize = 3;\n",
+                "\n",
+                "    fn len() -> usize;\n",
+                "\n",
+                "    fn is_empty() -> bool { ... }\n",
+                "}"
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_use_with_rename() -> crate::Result<()> {
+        assert_use_format(
+            r#"{"source": "raw::RawJson", "name": "Json", "id": 154, "is_glob": false}"#,
+            "pub use raw::RawJson as Json;",
+        )
+    }
+
+    #[test]
+    fn format_use_without_rename() -> crate::Result<()> {
+        assert_use_format(
+            r#"{"source": "crate::parse_error::JsonParseError", "name": "JsonParseError", "id": 343, "is_glob": false}"#,
+            "pub use crate::parse_error::JsonParseError;",
+        )
+    }
+
+    #[test]
+    fn format_use_glob() -> crate::Result<()> {
+        assert_use_format(
+            r#"{"source": "self::inner", "name": "inner", "id": 12, "is_glob": true}"#,
+            "pub use self::inner::*;",
+        )
+    }
+
+    fn assert_use_format(input: &str, expected: &str) -> crate::Result<()> {
+        let raw_json = nojson::RawJson::parse(input)?;
+        let mut buffer = Vec::new();
+        let mut formatter = UseFormatter::new(&mut buffer);
+        formatter.format(raw_json.value())?;
+        assert_eq!(String::from_utf8_lossy(&buffer), expected);
+        Ok(())
+    }
+
     fn empty_doc() -> crate::doc::CrateDoc {
-        let text = r#"{"root": 0, "index": {"0": {"name": "test", "visibility": "public", "inner": {"module": {"items": []}}, "docs": null, "deprecation": null}}}"#;
+        let text = r#"{"format_version": 57, "root": 0, "index": {"0": {"id": 0, "name": "test", "visibility": "public", "inner": {"module": {"items": []}}, "docs": null, "deprecation": null}}}"#;
         crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text).expect("bug")
     }
 }
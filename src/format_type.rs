@@ -1,9 +1,10 @@
 pub fn format_to_string(
     doc: &crate::doc::CrateDoc,
     ty: nojson::RawJsonValue,
+    strict: bool,
 ) -> crate::Result<String> {
     let mut buffer = Vec::new();
-    let mut formatter = TypeFormatter::new(&mut buffer, doc);
+    let mut formatter = TypeFormatter::new(&mut buffer, doc).strict(strict);
     formatter.format(ty)?;
     Ok(String::from_utf8(buffer).expect("bug"))
 }
@@ -11,13 +12,34 @@ pub fn format_to_string(
 #[derive(Debug)]
 pub struct TypeFormatter<'a, W> {
     writer: W,
-    #[expect(dead_code)]
-    doc: &'a crate::doc::CrateDoc,
+    /// `None` while the doc is being built
+    doc: Option<&'a crate::doc::CrateDoc>,
+    strict: bool,
 }
 
 impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
     pub fn new(writer: W, doc: &'a crate::doc::CrateDoc) -> Self {
-        Self { writer, doc }
+        Self {
+            writer,
+            doc: Some(doc),
+            strict: false,
+        }
+    }
+
+    /// Makes a formatter for use before the [`crate::doc::CrateDoc`] is available
+    pub fn without_doc(writer: W) -> Self {
+        Self {
+            writer,
+            doc: None,
+            strict: false,
+        }
+    }
+
+    /// Makes the formatter report unsupported JSON as an error (with its span)
+    /// instead of writing it as-is
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn format(&mut self, ty: nojson::RawJsonValue) -> crate::Result<()> {
@@ -26,7 +48,10 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
     }
 
     fn format_type(&mut self, ty: nojson::RawJsonValue) -> crate::Result<()> {
-        if let Some(generic) = ty.to_member("generic")?.get() {
+        if is_infer(ty)? {
+            write!(self.writer, "_")?;
+            Ok(())
+        } else if let Some(generic) = ty.to_member("generic")?.get() {
             self.format_generic(generic)
         } else if let Some(resolved) = ty.to_member("resolved_path")?.get() {
             self.format_resolved_path(resolved)
@@ -50,67 +75,262 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
             self.format_array(array)
         } else if let Some(function_pointer) = ty.to_member("function_pointer")?.get() {
             self.format_function_pointer(function_pointer)
+        } else if let Some(pat) = ty.to_member("pat")?.get() {
+            self.format_pat(pat)
         } else {
-            write!(self.writer, "{}", ty)?;
-            Ok(())
+            self.format_unsupported(ty, "type")
         }
     }
 
+    /// Writes JSON without a known rendering as-is (or reports it as an error in strict mode)
+    pub fn format_unsupported(
+        &mut self,
+        value: nojson::RawJsonValue,
+        description: &str,
+    ) -> crate::Result<()> {
+        if self.strict {
+            let error = value.invalid(format!("unsupported {description}"));
+            return Err(crate::Error::from(error).set_json_span(value));
+        }
+        write!(self.writer, "{}", value)?;
+        Ok(())
+    }
+
     fn format_generic(&mut self, generic: nojson::RawJsonValue) -> crate::Result<()> {
         write!(self.writer, "{}", generic.to_unquoted_string_str()?)?;
         Ok(())
     }
 
     fn format_resolved_path(&mut self, resolved: nojson::RawJsonValue) -> crate::Result<()> {
-        let path = resolved
+        self.format_path(resolved)
+    }
+
+    /// Formats a path with its generic args (e.g., `Vec<T>` or `Iterator<Item = u8>`)
+    pub fn format_path(&mut self, path_obj: nojson::RawJsonValue) -> crate::Result<()> {
+        let path = path_obj
             .to_member("path")?
             .required()?
             .to_unquoted_string_str()?;
+        write!(self.writer, "{}", path)?;
 
-        if let Some(args) = resolved.to_member("args")?.get()
+        if let Some(args) = path_obj.to_member("args")?.get()
             && !args.kind().is_null()
         {
-            self.format_resolved_path_with_args(path, args)
-        } else {
-            write!(self.writer, "{}", path)?;
-            Ok(())
+            self.format_generic_args(args)?;
         }
+        Ok(())
     }
 
-    fn format_resolved_path_with_args(
-        &mut self,
-        path: std::borrow::Cow<str>,
-        args: nojson::RawJsonValue,
-    ) -> crate::Result<()> {
-        write!(self.writer, "{}", path)?;
+    fn format_generic_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
+        if args.kind().is_string() {
+            // `"return_type_notation"` (e.g., `T::method(..): Send`)
+            if args.to_unquoted_string_str()? == "return_type_notation" {
+                write!(self.writer, "(..)")?;
+                return Ok(());
+            }
+            self.format_unsupported(args, "generic args")
+        } else if let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() {
+            self.format_angle_bracketed_args(angle_bracketed)
+        } else if let Some(parenthesized) = args.to_member("parenthesized")?.get() {
+            self.format_parenthesized_args(parenthesized)
+        } else {
+            self.format_unsupported(args, "generic args")
+        }
+    }
 
-        let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() else {
-            return Ok(());
-        };
+    /// Formats `(A, B) -> C` of `Fn` traits
+    fn format_parenthesized_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
+        write!(self.writer, "(")?;
+        for (i, input) in args
+            .to_member("inputs")?
+            .required()?
+            .to_array()?
+            .enumerate()
+        {
+            if i > 0 {
+                write!(self.writer, ", ")?;
+            }
+            self.format_type(input)?;
+        }
+        write!(self.writer, ")")?;
 
-        self.format_angle_bracketed_args(angle_bracketed)
+        if let Some(output) = args.to_member("output")?.get()
+            && !output.kind().is_null()
+        {
+            write!(self.writer, " -> ")?;
+            self.format_type(output)?;
+        }
+        Ok(())
     }
 
     fn format_angle_bracketed_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
+        let args_list: Vec<_> = match args.to_member("args")?.get() {
+            Some(list) => list.to_array()?.collect(),
+            None => Vec::new(),
+        };
+        let constraints: Vec<_> = match args.to_member("constraints")?.get() {
+            Some(list) => list.to_array()?.collect(),
+            None => Vec::new(),
+        };
+        if args_list.is_empty() && constraints.is_empty() {
+            return Ok(());
+        }
+
+        let has_args = !args_list.is_empty();
         write!(self.writer, "<")?;
-        let args_array = args.to_member("args")?.required()?;
-        for (i, arg) in args_array.to_array()?.enumerate() {
+        for (i, arg) in args_list.into_iter().enumerate() {
             if i > 0 {
                 write!(self.writer, ", ")?;
             }
 
-            if let Some(arg_type) = arg.to_member("type")?.get() {
+            if is_infer(arg)? {
+                write!(self.writer, "_")?;
+            } else if let Some(arg_type) = arg.to_member("type")?.get() {
                 self.format_type(arg_type)?;
             } else if let Some(lifetime) = arg.to_member("lifetime")?.get() {
                 let lifetime_str = lifetime.to_unquoted_string_str()?;
                 write!(self.writer, "{}", lifetime_str)?;
+            } else if let Some(constant) = arg.to_member("const")?.get() {
+                self.format_constant(constant)?;
+            } else {
+                self.format_unsupported(arg, "generic arg")?;
+            }
+        }
+        for (i, constraint) in constraints.into_iter().enumerate() {
+            if i > 0 || has_args {
+                write!(self.writer, ", ")?;
             }
+            self.format_assoc_item_constraint(constraint)?;
         }
         write!(self.writer, ">")?;
 
         Ok(())
     }
 
+    /// Formats `Name = Type` or `Name: Bounds` in generic args
+    fn format_assoc_item_constraint(
+        &mut self,
+        constraint: nojson::RawJsonValue,
+    ) -> crate::Result<()> {
+        let name = constraint
+            .to_member("name")?
+            .required()?
+            .to_unquoted_string_str()?;
+        write!(self.writer, "{}", name)?;
+
+        if let Some(args) = constraint.to_member("args")?.get()
+            && !args.kind().is_null()
+        {
+            self.format_generic_args(args)?;
+        }
+
+        let Some(binding) = constraint.to_member("binding")?.get() else {
+            return Ok(());
+        };
+        if let Some(equality) = binding.to_member("equality")?.get() {
+            write!(self.writer, " = ")?;
+            self.format_term(equality)?;
+        } else if let Some(bounds) = binding.to_member("constraint")?.get() {
+            write!(self.writer, ": ")?;
+            self.format_bounds(bounds)?;
+        } else {
+            self.format_unsupported(binding, "constraint binding")?;
+        }
+        Ok(())
+    }
+
+    /// Formats a type or a constant on the right-hand side of `=`
+    pub fn format_term(&mut self, term: nojson::RawJsonValue) -> crate::Result<()> {
+        if let Some(ty) = term.to_member("type")?.get() {
+            self.format_type(ty)
+        } else if let Some(constant) = term.to_member("constant")?.get() {
+            self.format_constant(constant)
+        } else {
+            self.format_unsupported(term, "term")
+        }
+    }
+
+    fn format_constant(&mut self, constant: nojson::RawJsonValue) -> crate::Result<()> {
+        let expr = constant
+            .to_member("expr")?
+            .required()?
+            .to_unquoted_string_str()?;
+        write!(self.writer, "{}", expr)?;
+        Ok(())
+    }
+
+    /// Formats generic bounds joined with ` + ` (e.g., `for<'a> Fn(&'a str) + ?Sized + 'static`)
+    pub fn format_bounds(&mut self, bounds: nojson::RawJsonValue) -> crate::Result<()> {
+        for (i, bound) in bounds.to_array()?.enumerate() {
+            if i > 0 {
+                write!(self.writer, " + ")?;
+            }
+
+            if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
+                if let Some(params) = trait_bound.to_member("generic_params")?.get() {
+                    self.format_higher_ranked_params(params)?;
+                }
+                let modifier = trait_bound
+                    .to_member("modifier")?
+                    .get()
+                    .map(|m| m.to_unquoted_string_str())
+                    .transpose()?;
+                match modifier.as_deref() {
+                    Some("maybe") => write!(self.writer, "?")?,
+                    Some("maybe_const") => write!(self.writer, "~const ")?,
+                    _ => {}
+                }
+                self.format_path(trait_bound.to_member("trait")?.required()?)?;
+            } else if let Some(outlives) = bound.to_member("outlives")?.get() {
+                write!(self.writer, "{}", outlives.to_unquoted_string_str()?)?;
+            } else if let Some(captures) = bound.to_member("use")?.get() {
+                write!(self.writer, "use<")?;
+                for (i, capture) in captures.to_array()?.enumerate() {
+                    if i > 0 {
+                        write!(self.writer, ", ")?;
+                    }
+                    // Older formats use plain strings, newer ones `{"lifetime": ..}` or `{"param": ..}`
+                    let name = if capture.kind().is_string() {
+                        capture.to_unquoted_string_str()?
+                    } else if let Some(lifetime) = capture.to_member("lifetime")?.get() {
+                        lifetime.to_unquoted_string_str()?
+                    } else {
+                        capture
+                            .to_member("param")?
+                            .required()?
+                            .to_unquoted_string_str()?
+                    };
+                    write!(self.writer, "{}", name)?;
+                }
+                write!(self.writer, ">")?;
+            } else {
+                self.format_unsupported(bound, "bound")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats `for<'a, ..> ` of higher-ranked bounds (nothing if there are no params)
+    pub fn format_higher_ranked_params(
+        &mut self,
+        params: nojson::RawJsonValue,
+    ) -> crate::Result<()> {
+        let names = params
+            .to_array()?
+            .map(|param| {
+                param
+                    .to_member("name")?
+                    .required()?
+                    .to_unquoted_string_str()
+                    .map(|x| x.into_owned())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !names.is_empty() {
+            write!(self.writer, "for<{}> ", names.join(", "))?;
+        }
+        Ok(())
+    }
+
     fn format_primitive(&mut self, primitive: nojson::RawJsonValue) -> crate::Result<()> {
         let formatted = primitive.to_unquoted_string_str()?;
         write!(self.writer, "{}", formatted)?;
@@ -166,16 +386,39 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
             .required()?
             .to_unquoted_string_str()?;
         let self_type = qualified_path.to_member("self_type")?.required()?;
-        let trait_info = qualified_path.to_member("trait")?.required()?;
-        let trait_path = trait_info
-            .to_member("path")?
-            .required()?
-            .to_unquoted_string_str()?;
 
         write!(self.writer, "<")?;
         self.format_type(self_type)?;
-        write!(self.writer, " as {}>::{}", trait_path, name)?;
+        // The trait is `null` for inherent associated types
+        if let Some(trait_info) = qualified_path.to_member("trait")?.get()
+            && !trait_info.kind().is_null()
+        {
+            let mut trait_path = trait_info
+                .to_member("path")?
+                .required()?
+                .to_unquoted_string_str()?
+                .into_owned();
+            // rustdoc leaves the path empty for some traits (e.g., `<Self as TryFrom<T>>::Error`)
+            if trait_path.is_empty()
+                && let Some(doc) = self.doc
+                && let Some(path) = doc.item_path(trait_info.to_member("id")?.required()?)?
+            {
+                trait_path = path.name().to_owned();
+            }
+            write!(self.writer, " as {}", trait_path)?;
+            if let Some(args) = trait_info.to_member("args")?.get()
+                && !args.kind().is_null()
+            {
+                self.format_generic_args(args)?;
+            }
+        }
+        write!(self.writer, ">::{}", name)?;
 
+        if let Some(args) = qualified_path.to_member("args")?.get()
+            && !args.kind().is_null()
+        {
+            self.format_generic_args(args)?;
+        }
         Ok(())
     }
 
@@ -197,34 +440,14 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
         write!(self.writer, "dyn ")?;
 
         let traits = dyn_trait.to_member("traits")?.required()?;
-        let mut first = true;
-
-        for trait_obj in traits.to_array()? {
-            if !first {
+        for (i, poly_trait) in traits.to_array()?.enumerate() {
+            if i > 0 {
                 write!(self.writer, " + ")?;
             }
-
-            let trait_info = trait_obj.to_member("trait")?.required()?;
-            let trait_path = trait_info
-                .to_member("path")?
-                .required()?
-                .to_unquoted_string_str()?;
-
-            write!(self.writer, "{}", trait_path)?;
-
-            // Handle generic args if present
-            if let Some(args) = trait_info.to_member("args")?.get()
-                && !args.kind().is_null()
-            {
-                let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() else {
-                    first = false;
-                    continue;
-                };
-
-                self.format_angle_bracketed_args(angle_bracketed)?;
+            if let Some(params) = poly_trait.to_member("generic_params")?.get() {
+                self.format_higher_ranked_params(params)?;
             }
-
-            first = false;
+            self.format_path(poly_trait.to_member("trait")?.required()?)?;
         }
 
         // Add lifetime if present
@@ -240,95 +463,7 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
 
     fn format_impl_trait(&mut self, impl_trait: nojson::RawJsonValue) -> crate::Result<()> {
         write!(self.writer, "impl ")?;
-
-        let mut first = true;
-        for trait_obj in impl_trait.to_array()? {
-            if !first {
-                write!(self.writer, " + ")?;
-            }
-
-            // Handle lifetime bounds (e.g., 'outlives': "'_")
-            if let Some(outlives) = trait_obj.to_member("outlives")?.get() {
-                let lifetime_str = outlives.to_unquoted_string_str()?;
-                write!(self.writer, "{}", lifetime_str)?;
-                first = false;
-                continue;
-            }
-
-            // Handle trait bounds
-            let trait_bound = trait_obj.to_member("trait_bound")?.required()?;
-            let trait_info = trait_bound.to_member("trait")?.required()?;
-            let trait_path = trait_info
-                .to_member("path")?
-                .required()?
-                .to_unquoted_string_str()?;
-
-            write!(self.writer, "{}", trait_path)?;
-
-            // Handle generic args and constraints
-            if let Some(args) = trait_info.to_member("args")?.get()
-                && !args.kind().is_null()
-            {
-                self.format_impl_trait_args(args)?;
-            }
-
-            first = false;
-        }
-
-        Ok(())
-    }
-
-    fn format_impl_trait_args(&mut self, args: nojson::RawJsonValue) -> crate::Result<()> {
-        if let Some(angle_bracketed) = args.to_member("angle_bracketed")?.get() {
-            write!(self.writer, "<")?;
-
-            let args_array = angle_bracketed.to_member("args")?;
-            let mut has_args = false;
-            if let Some(args_list) = args_array.get() {
-                let mut first = true;
-                for arg in args_list.to_array()? {
-                    if !first {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    if let Some(arg_type) = arg.to_member("type")?.get() {
-                        self.format_type(arg_type)?;
-                    }
-
-                    first = false;
-                    has_args = true;
-                }
-            }
-
-            let constraints = angle_bracketed.to_member("constraints")?;
-            if let Some(constraints_list) = constraints.get() {
-                let mut first = true;
-                for constraint in constraints_list.to_array()? {
-                    if has_args || !first {
-                        write!(self.writer, ", ")?;
-                    }
-
-                    let name = constraint
-                        .to_member("name")?
-                        .required()?
-                        .to_unquoted_string_str()?;
-                    write!(self.writer, "{}", name)?;
-
-                    if let Some(binding) = constraint.to_member("binding")?.get()
-                        && let Some(equality) = binding.to_member("equality")?.get()
-                    {
-                        write!(self.writer, " = ")?;
-                        self.format_type(equality.to_member("type")?.required()?)?;
-                    }
-
-                    first = false;
-                }
-            }
-
-            write!(self.writer, ">")?;
-        }
-
-        Ok(())
+        self.format_bounds(impl_trait)
     }
 
     fn format_slice(&mut self, slice: nojson::RawJsonValue) -> crate::Result<()> {
@@ -354,6 +489,17 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
     fn format_function_pointer(&mut self, fn_ptr: nojson::RawJsonValue) -> crate::Result<()> {
         let sig = fn_ptr.to_member("sig")?.required()?;
 
+        if let Some(params) = fn_ptr.to_member("generic_params")?.get() {
+            self.format_higher_ranked_params(params)?;
+        }
+        if let Some(header) = fn_ptr.to_member("header")?.get() {
+            let is_unsafe: bool = header.to_member("is_unsafe")?.required()?.try_into()?;
+            if is_unsafe {
+                write!(self.writer, "unsafe ")?;
+            }
+            self.format_abi(header.to_member("abi")?.required()?)?;
+        }
+
         // Format function pointer signature
         write!(self.writer, "fn(")?;
 
@@ -370,6 +516,13 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
                 self.format_type(param_type)?;
             }
         }
+        let is_c_variadic: bool = sig.to_member("is_c_variadic")?.required()?.try_into()?;
+        if is_c_variadic {
+            if inputs.to_array()?.next().is_some() {
+                write!(self.writer, ", ")?;
+            }
+            write!(self.writer, "...")?;
+        }
 
         write!(self.writer, ")")?;
 
@@ -384,6 +537,56 @@ impl<'a, W: std::io::Write> TypeFormatter<'a, W> {
 
         Ok(())
     }
+
+    /// Writes `extern "ABI" ` of a function header (nothing for the default Rust ABI)
+    pub fn format_abi(&mut self, abi: nojson::RawJsonValue) -> crate::Result<()> {
+        if abi.kind().is_string() {
+            // e.g., `"Rust"`
+            if abi.to_unquoted_string_str()? != "Rust" {
+                self.format_unsupported(abi, "ABI")?;
+            }
+            return Ok(());
+        }
+
+        let Some((name, value)) = abi.to_object()?.next() else {
+            return self.format_unsupported(abi, "ABI");
+        };
+        let name = name.to_unquoted_string_str()?;
+        let abi_name = match name.as_ref() {
+            "Other" => value.to_unquoted_string_str()?.into_owned(),
+            "C" | "Cdecl" | "Stdcall" | "Fastcall" | "Aapcs" | "Win64" | "SysV64" | "System" => {
+                let mut abi_name = if name == "C" {
+                    "C".to_owned()
+                } else {
+                    name.to_ascii_lowercase()
+                };
+                let unwind: bool = value.to_member("unwind")?.required()?.try_into()?;
+                if unwind {
+                    abi_name.push_str("-unwind");
+                }
+                abi_name
+            }
+            _ => return self.format_unsupported(abi, "ABI"),
+        };
+        write!(self.writer, "extern \"{}\" ", abi_name)?;
+        Ok(())
+    }
+
+    /// Formats pattern types (e.g., `u32 is 1..`)
+    fn format_pat(&mut self, pat: nojson::RawJsonValue) -> crate::Result<()> {
+        self.format_type(pat.to_member("type")?.required()?)?;
+        let pattern = pat
+            .to_member("__pat_unstable_do_not_use")?
+            .required()?
+            .to_unquoted_string_str()?;
+        write!(self.writer, " is {}", pattern)?;
+        Ok(())
+    }
+}
+
+/// Returns whether `value` is the unit variant `"infer"` (`_`) of types and generic args
+fn is_infer(value: nojson::RawJsonValue) -> crate::Result<bool> {
+    Ok(value.kind().is_string() && value.to_unquoted_string_str()? == "infer")
 }
 
 #[cfg(test)]
@@ -609,16 +812,85 @@ mod tests {
         )
     }
 
+    #[test]
+    fn format_extern_unsafe_higher_ranked_function_pointer() -> crate::Result<()> {
+        assert_format(
+            r#"{"function_pointer":{"sig":{"inputs":[["_",{"borrowed_ref":{"lifetime":"'a","is_mutable":false,"type":{"primitive":"u8"}}}]],"output":null,"is_c_variadic":true},"generic_params":[{"name":"'a","kind":{"lifetime":{"outlives":[]}}}],"header":{"is_const":false,"is_unsafe":true,"is_async":false,"abi":{"C":{"unwind":false}}}}}"#,
+            "for<'a> unsafe extern \"C\" fn(&'a u8, ...)",
+        )
+    }
+
+    #[test]
+    fn format_variadic_function_pointer_without_inputs() -> crate::Result<()> {
+        assert_format(
+            r#"{"function_pointer":{"sig":{"inputs":[],"output":null,"is_c_variadic":true},"generic_params":[],"header":{"is_const":false,"is_unsafe":true,"is_async":false,"abi":{"C":{"unwind":false}}}}}"#,
+            "unsafe extern \"C\" fn(...)",
+        )
+    }
+
+    #[test]
+    fn format_parenthesized_args() -> crate::Result<()> {
+        assert_format(
+            r#"{"dyn_trait":{"traits":[{"trait":{"path":"Fn","id":1,"args":{"parenthesized":{"inputs":[{"primitive":"u8"},{"generic":"T"}],"output":{"primitive":"bool"}}}},"generic_params":[]}],"lifetime":null}}"#,
+            "dyn Fn(u8, T) -> bool",
+        )
+    }
+
+    #[test]
+    fn format_infer_and_const_args() -> crate::Result<()> {
+        assert_format(
+            r#"{"resolved_path":{"path":"Buf","id":1,"args":{"angle_bracketed":{"args":["infer",{"const":{"expr":"{ N + 1 }","value":null,"is_literal":false}}],"constraints":[]}}}}"#,
+            "Buf<_, { N + 1 }>",
+        )?;
+        assert_format(r#""infer""#, "_")
+    }
+
+    #[test]
+    fn format_pat() -> crate::Result<()> {
+        assert_format(
+            r#"{"pat":{"type":{"primitive":"u32"},"__pat_unstable_do_not_use":"1.."}}"#,
+            "u32 is 1..",
+        )
+    }
+
+    #[test]
+    fn format_qualified_path_with_args_and_without_trait() -> crate::Result<()> {
+        assert_format(
+            r#"{"qualified_path":{"name":"Iter","args":{"angle_bracketed":{"args":[{"lifetime":"'a"}],"constraints":[]}},"self_type":{"generic":"T"},"trait":{"path":"Collection","id":1,"args":{"angle_bracketed":{"args":[{"type":{"primitive":"u8"}}],"constraints":[]}}}}}"#,
+            "<T as Collection<u8>>::Iter<'a>",
+        )?;
+        assert_format(
+            r#"{"qualified_path":{"name":"Assoc","args":null,"self_type":{"generic":"Self"},"trait":null}}"#,
+            "<Self>::Assoc",
+        )
+    }
+
+    #[test]
+    fn report_unsupported_type_in_strict_mode() -> crate::Result<()> {
+        let doc = empty_doc();
+        let raw_json = nojson::RawJson::parse(r#"{"slice":{"unknown":{}}}"#)?;
+        assert_eq!(
+            format_to_string(&doc, raw_json.value(), false)?,
+            r#"[{"unknown":{}}]"#
+        );
+
+        let Err(crate::Error::Json(error)) = format_to_string(&doc, raw_json.value(), true) else {
+            panic!("expected an error");
+        };
+        assert_eq!(error.span, Some(9..23));
+        Ok(())
+    }
+
     fn assert_format(input: &str, expected: &str) -> crate::Result<()> {
         let doc = empty_doc();
         let raw_json = nojson::RawJson::parse(input)?;
-        let formatted = format_to_string(&doc, raw_json.value())?;
+        let formatted = format_to_string(&doc, raw_json.value(), false)?;
         assert_eq!(formatted, expected);
         Ok(())
     }
 
     fn empty_doc() -> crate::doc::CrateDoc {
-        let text = r#"{"root": 0, "index": {"0": {"name": "test", "visibility": "public", "inner": {"module": {"items": []}}, "docs": null, "deprecation": null}}}"#;
+        let text = r#"{"format_version": 57, "root": 0, "index": {"0": {"id": 0, "name": "test", "visibility": "public", "inner": {"module": {"items": []}}, "docs": null, "deprecation": null}}}"#;
         crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text).expect("bug")
     }
 }
@@ -2,11 +2,21 @@
 pub struct FunctionView<'a> {
     doc: &'a crate::doc::CrateDoc,
     item: &'a crate::doc::Item,
+    strict: bool,
 }
 
 impl<'a> FunctionView<'a> {
     pub fn new(doc: &'a crate::doc::CrateDoc, item: &'a crate::doc::Item) -> Self {
-        Self { doc, item }
+        Self {
+            doc,
+            item,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn name(&self) -> &str {
@@ -14,8 +24,8 @@ impl<'a> FunctionView<'a> {
     }
 
     pub fn signature(&self) -> crate::Result<String> {
-        let inner = self.item.inner(&self.doc.json);
-        crate::format_item::format_function_to_string(self.doc, self.name(), inner)
+        let inner = self.item.inner(self.doc.json());
+        crate::format_item::format_function_to_string(self.doc, self.name(), inner, self.strict)
     }
 }
 
@@ -23,11 +33,21 @@ impl<'a> FunctionView<'a> {
 pub struct FieldView<'a> {
     doc: &'a crate::doc::CrateDoc,
     item: &'a crate::doc::Item,
+    strict: bool,
 }
 
 impl<'a> FieldView<'a> {
     pub fn new(doc: &'a crate::doc::CrateDoc, item: &'a crate::doc::Item) -> Self {
-        Self { doc, item }
+        Self {
+            doc,
+            item,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn name(&self) -> &str {
@@ -35,8 +55,8 @@ impl<'a> FieldView<'a> {
     }
 
     pub fn ty(&self) -> crate::Result<String> {
-        let inner = self.item.inner(&self.doc.json);
-        crate::format_type::format_to_string(self.doc, inner)
+        let inner = self.item.inner(self.doc.json());
+        crate::format_type::format_to_string(self.doc, inner, self.strict)
     }
 }
 
@@ -56,7 +76,7 @@ impl<'a> ModuleView<'a> {
     }
 
     pub fn child_count(&self) -> crate::Result<usize> {
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
         let items = inner.to_member("items")?.required()?;
         Ok(items.to_array()?.count())
     }
@@ -78,7 +98,7 @@ impl<'a> ProcMacroView<'a> {
     }
 
     pub fn derive_attribute(&self) -> crate::Result<String> {
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
         let kind = inner
             .to_member("kind")?
             .required()?
@@ -107,7 +127,7 @@ impl<'a> PrimitiveView<'a> {
     }
 
     pub fn impls(&self) -> crate::Result<Vec<u64>> {
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
         let impls = inner.to_member("impls")?.required()?;
 
         let mut impl_ids = Vec::new();
@@ -124,16 +144,26 @@ impl<'a> PrimitiveView<'a> {
 pub struct TypeView<'a> {
     doc: &'a crate::doc::CrateDoc,
     item: &'a crate::doc::Item,
+    strict: bool,
 }
 
 impl<'a> TypeView<'a> {
     pub fn new(doc: &'a crate::doc::CrateDoc, item: &'a crate::doc::Item) -> Self {
-        Self { doc, item }
+        Self {
+            doc,
+            item,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn name(&self) -> crate::Result<String> {
         let name = self.item.name.as_ref().expect("bug");
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
 
         // Check if there are generics to append
         if let Some(generics) = inner.to_member("generics")?.get() {
@@ -156,13 +186,13 @@ impl<'a> TypeView<'a> {
     }
 
     pub fn ty(&self) -> crate::Result<Option<String>> {
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
         let ty = inner.to_member("type")?.required()?;
         if ty.kind().is_null() {
             return Ok(None);
         }
 
-        crate::format_type::format_to_string(self.doc, ty).map(Some)
+        crate::format_type::format_to_string(self.doc, ty, self.strict).map(Some)
     }
 }
 
@@ -170,11 +200,21 @@ impl<'a> TypeView<'a> {
 pub struct ConstantView<'a> {
     doc: &'a crate::doc::CrateDoc,
     item: &'a crate::doc::Item,
+    strict: bool,
 }
 
 impl<'a> ConstantView<'a> {
     pub fn new(doc: &'a crate::doc::CrateDoc, item: &'a crate::doc::Item) -> Self {
-        Self { doc, item }
+        Self {
+            doc,
+            item,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     pub fn name(&self) -> &str {
@@ -182,8 +222,59 @@ impl<'a> ConstantView<'a> {
     }
 
     pub fn ty(&self) -> crate::Result<String> {
-        let inner = self.item.inner(&self.doc.json);
+        let inner = self.item.inner(self.doc.json());
         let ty = inner.to_member("type")?.required()?;
-        crate::format_type::format_to_string(self.doc, ty)
+        crate::format_type::format_to_string(self.doc, ty, self.strict)
+    }
+}
+
+#[derive(Debug)]
+pub struct ImplView<'a> {
+    doc: &'a crate::doc::CrateDoc,
+    item: &'a crate::doc::Item,
+    strict: bool,
+}
+
+impl<'a> ImplView<'a> {
+    pub fn new(doc: &'a crate::doc::CrateDoc, item: &'a crate::doc::Item) -> Self {
+        Self {
+            doc,
+            item,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn is_inherent(&self) -> crate::Result<bool> {
+        let inner = self.item.inner(self.doc.json());
+        Ok(inner.to_member("trait")?.required()?.kind().is_null())
+    }
+
+    pub fn is_generated(&self) -> crate::Result<bool> {
+        let inner = self.item.inner(self.doc.json());
+        Ok(crate::doc::is_generated_impl(inner)?)
+    }
+
+    pub fn has_public_items(&self) -> crate::Result<bool> {
+        let inner = self.item.inner(self.doc.json());
+        for item_id in inner.to_member("items")?.required()?.to_array()? {
+            let item_value = self.doc.items.get(self.doc.json(), item_id)?;
+            if crate::doc::Item::try_from(item_value)?.is_public {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn header(&self) -> crate::Result<String> {
+        crate::format_item::format_impl_header_to_string(self.doc, self.item, self.strict)
+    }
+
+    pub fn signature(&self) -> crate::Result<String> {
+        crate::format_item::format_impl_to_string(self.doc, self.item, self.strict)
     }
 }
@@ -2,6 +2,12 @@
 pub struct JsonValueIndex(usize);
 
 impl JsonValueIndex {
+    /// Makes an index from a value previously returned by [`JsonValueIndex::get`]
+    /// (the indices are stable as long as the JSON text is the same)
+    pub fn new(index: usize) -> Self {
+        Self(index)
+    }
+
     pub fn get(self) -> usize {
         self.0
     }
@@ -15,6 +21,115 @@ impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for JsonValueIndex
     }
 }
 
+/// Cursor over JSON text for picking out a few members without parsing the whole text
+///
+/// Values are skipped by matching brackets, so this is much faster than
+/// [`nojson::RawJson::parse`] for large documents, but malformed JSON is not detected.
+#[derive(Debug)]
+pub struct JsonScanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    /// Moves into the object at the cursor, whose members can then be visited by [`Self::next_key`]
+    pub fn enter_object(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        self.eat(b'{')
+    }
+
+    /// Returns the (still escaped) name of the next member of the current object and moves to its
+    /// value, or returns `None` at the end of the object
+    pub fn next_key(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        if self.eat(b',').is_some() {
+            self.skip_whitespace();
+        }
+        if self.peek()? != b'"' {
+            return None;
+        }
+        let key = self.skip_string()?;
+        self.skip_whitespace();
+        self.eat(b':')?;
+        Some(&key[1..key.len() - 1])
+    }
+
+    /// Skips the value at the cursor and returns its raw text
+    pub fn take_value(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek()? {
+            b'"' => {
+                self.skip_string()?;
+            }
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.pos += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+            }
+        }
+        Some(&self.text[start..self.pos])
+    }
+
+    fn skip_string(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        self.eat(b'"')?;
+        let bytes = self.text.as_bytes();
+        loop {
+            match *bytes.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(&self.text[start..self.pos]);
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+}
+
 pub fn format_parse_error(text: &str, error: &nojson::JsonParseError) -> String {
     let (line_num, column_num) = error
         .get_line_and_column_numbers(text)
@@ -0,0 +1,167 @@
+/// Breaks lines longer than `width` columns at bracketed lists, rustfmt-style
+///
+/// The contents of a `(..)`, `<..>` or `[..]` group are put on separate, further indented lines
+/// with trailing commas. The longest group on a line is broken first, and lines that are
+/// still too long are broken again. Short lines are left untouched.
+pub fn wrap_lines(text: &str, width: usize) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        wrap_line(line.to_owned(), width, &mut lines);
+    }
+    let mut wrapped = lines.join("\n");
+    if text.ends_with('\n') {
+        wrapped.push('\n');
+    }
+    wrapped
+}
+
+fn wrap_line(line: String, width: usize, lines: &mut Vec<String>) {
+    if line.chars().count() <= width {
+        lines.push(line);
+        return;
+    }
+    let Some((open, close)) = select_group(&line) else {
+        lines.push(line);
+        return;
+    };
+
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let item_indent = format!("{indent}    ");
+    let items = split_top_level(&line[open + 1..close], ',');
+
+    wrap_line(line[..=open].to_owned(), width, lines);
+    for item in items {
+        wrap_line(format!("{item_indent}{item},"), width, lines);
+    }
+    wrap_line(format!("{indent}{}", &line[close..]), width, lines);
+}
+
+/// Returns the byte positions of the brackets of the longest (first if tied) group to break
+fn select_group(line: &str) -> Option<(usize, usize)> {
+    top_level_groups(line)
+        .into_iter()
+        .filter(|&(open, close)| !line[open + 1..close].trim().is_empty())
+        .rev()
+        .max_by_key(|&(open, close)| close - open)
+}
+
+/// Returns the outermost bracket pairs of `line` (unbalanced brackets are ignored)
+fn top_level_groups(line: &str) -> Vec<(usize, usize)> {
+    let mut groups = Vec::new();
+    let mut stack = Vec::new();
+    let mut prev = ' ';
+    let mut chars = line.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                // Skip string literals (e.g., in constant values)
+                let mut escaped = false;
+                for (_, c) in chars.by_ref() {
+                    if c == '"' && !escaped {
+                        break;
+                    }
+                    escaped = c == '\\' && !escaped;
+                }
+            }
+            '(' | '<' | '[' | '{' => stack.push((c, i)),
+            // `->` and `=>` are not closing brackets
+            '>' if prev == '-' || prev == '=' => {}
+            ')' | '>' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    '>' => '<',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if let Some(pos) = stack.iter().rposition(|&(open, _)| open == expected) {
+                    let (open, start) = stack[pos];
+                    stack.truncate(pos);
+                    if stack.is_empty() && open != '{' {
+                        groups.push((start, i));
+                    }
+                }
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    groups
+}
+
+/// Splits `text` at `separator`s outside of brackets and string literals, dropping empty items
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut prev = ' ';
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            if c == '"' && !escaped {
+                in_string = false;
+            }
+            escaped = c == '\\' && !escaped;
+            prev = c;
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '<' | '[' | '{' => depth += 1,
+            '>' if prev == '-' || prev == '=' => {}
+            ')' | '>' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ if c == separator && depth == 0 => {
+                items.push(text[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    items.push(text[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_long_signatures() {
+        let short = "fn new(name: &str) -> Self";
+        assert_eq!(wrap_lines(short, 40), short);
+
+        assert_eq!(
+            wrap_lines(
+                "pub fn route<H>(self, path: &str, handler: H) -> Result<Router<S>, Error>\nwhere\n    H: Handler",
+                50
+            ),
+            concat!(
+                "pub fn route<H>(\n",
+                "    self,\n",
+                "    path: &str,\n",
+                "    handler: H,\n",
+                ") -> Result<Router<S>, Error>\n",
+                "where\n",
+                "    H: Handler"
+            )
+        );
+
+        assert_eq!(
+            wrap_lines(
+                "    fn call(&self, f: impl Fn(u8) -> HashMap<String, Vec<(u8, u16)>>);",
+                36
+            ),
+            concat!(
+                "    fn call(\n",
+                "        &self,\n",
+                "        f: impl Fn(u8) -> HashMap<\n",
+                "            String,\n",
+                "            Vec<(u8, u16)>,\n",
+                "        >,\n",
+                "    );"
+            )
+        );
+    }
+}
@@ -1,11 +1,27 @@
+pub mod api_diff;
+pub mod cache;
+pub mod command_api_diff;
+pub mod command_api_snapshot;
 pub mod command_build_doc;
+pub mod command_cache;
+pub mod command_coverage;
+pub mod command_lint;
 pub mod command_main;
+pub mod compat;
+pub mod coverage;
 pub mod doc;
+pub mod format_generics;
 pub mod format_item;
 pub mod format_type;
 pub mod item_view;
 pub mod json;
+pub mod layout;
+pub mod line_diff;
+pub mod lint;
 pub mod markdown;
+pub mod parallel;
+pub mod search;
+pub mod workspace;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
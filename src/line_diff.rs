@@ -0,0 +1,288 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Delete,
+    Insert,
+}
+
+/// Formats the differences between two texts in the unified diff format
+///
+/// Returns an empty string if the texts have the same lines.
+pub fn unified_diff(
+    old_text: &str,
+    new_text: &str,
+    old_label: &str,
+    new_label: &str,
+    context: usize,
+) -> String {
+    let old: Vec<&str> = old_text.lines().collect();
+    let new: Vec<&str> = new_text.lines().collect();
+    let edits = shortest_edit_script(&old, &new);
+    if edits.iter().all(|&edit| edit == Edit::Keep) {
+        return String::new();
+    }
+
+    // Line positions (0-based) in the old and new texts before each edit
+    let mut positions = Vec::with_capacity(edits.len() + 1);
+    let (mut i, mut j) = (0, 0);
+    for &edit in &edits {
+        positions.push((i, j));
+        match edit {
+            Edit::Keep => (i, j) = (i + 1, j + 1),
+            Edit::Delete => i += 1,
+            Edit::Insert => j += 1,
+        }
+    }
+    positions.push((i, j));
+
+    let mut output = format!("--- {old_label}\n+++ {new_label}\n");
+    let mut k = 0;
+    while k < edits.len() {
+        if edits[k] == Edit::Keep {
+            k += 1;
+            continue;
+        }
+
+        // Extend the hunk while changes are separated by at most `2 * context` kept lines
+        let start = k.saturating_sub(context);
+        let mut end = k;
+        let mut last_change = k;
+        while end < edits.len() && end - last_change <= 2 * context + 1 {
+            if edits[end] != Edit::Keep {
+                last_change = end;
+            }
+            end += 1;
+        }
+        let end = (last_change + 1 + context).min(edits.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        ));
+        for (&edit, &(i, j)) in edits[start..end].iter().zip(&positions[start..end]) {
+            match edit {
+                Edit::Keep => output.push_str(&format!(" {}\n", old[i])),
+                Edit::Delete => output.push_str(&format!("-{}\n", old[i])),
+                Edit::Insert => output.push_str(&format!("+{}\n", new[j])),
+            }
+        }
+        k = end;
+    }
+    output
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    // Empty ranges refer to the line before them
+    let start = if len == 0 { start } else { start + 1 };
+    if len == 1 {
+        start.to_string()
+    } else {
+        format!("{start},{len}")
+    }
+}
+
+/// Computes a minimal edit script with the linear space variant of Myers' O(ND) algorithm
+///
+/// Deletions are placed before insertions within each run of changed lines.
+fn shortest_edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    // Furthest reaching x on each diagonal (offset so that negative diagonals fit)
+    let len = old.len() + new.len() + 4;
+    let mut forward = vec![0isize; len];
+    let mut backward = vec![0isize; len];
+    let mut edits = Vec::with_capacity(old.len().max(new.len()));
+    push_edits(old, new, &mut forward, &mut backward, &mut edits);
+
+    for run in edits.chunk_by_mut(|a, b| (*a == Edit::Keep) == (*b == Edit::Keep)) {
+        run.sort_by_key(|&edit| edit == Edit::Insert);
+    }
+    edits
+}
+
+/// Appends the edits from `old` to `new`, splitting the texts at the middle of an optimal path
+fn push_edits(
+    old: &[&str],
+    new: &[&str],
+    forward: &mut [isize],
+    backward: &mut [isize],
+    edits: &mut Vec<Edit>,
+) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (old, new) = (&old[prefix..], &new[prefix..]);
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old, new) = (&old[..old.len() - suffix], &new[..new.len() - suffix]);
+
+    edits.extend(std::iter::repeat_n(Edit::Keep, prefix));
+    if old.is_empty() {
+        edits.extend(std::iter::repeat_n(Edit::Insert, new.len()));
+    } else if new.is_empty() {
+        edits.extend(std::iter::repeat_n(Edit::Delete, old.len()));
+    } else {
+        let (x, y) = middle_snake(old, new, forward, backward);
+        push_edits(&old[..x], &new[..y], forward, backward, edits);
+        push_edits(&old[x..], &new[y..], forward, backward, edits);
+    }
+    edits.extend(std::iter::repeat_n(Edit::Keep, suffix));
+}
+
+/// Returns a point on an optimal path from the start to the end of two non-empty texts
+/// that differ in their first and last lines
+///
+/// The path is searched from both ends at once, and only the furthest reaching points of the
+/// current step are kept.
+fn middle_snake(
+    old: &[&str],
+    new: &[&str],
+    forward: &mut [isize],
+    backward: &mut [isize],
+) -> (usize, usize) {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let delta = n - m;
+    let is_odd = delta % 2 != 0;
+    let offset = (forward.len() / 2) as isize;
+    let at = |k: isize| (k + offset) as usize;
+    forward[at(1)] = 0;
+    backward[at(1)] = 0;
+
+    for d in 0..=(n + m + 1) / 2 {
+        for k in (-d..=d).rev().step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let start = (x, y);
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+            // The backward diagonal `delta - k` has been searched for `d - 1` steps
+            if is_odd && (k - delta).abs() < d && x + backward[at(delta - k)] >= n {
+                return (start.0 as usize, start.1 as usize);
+            }
+        }
+        for k in (-d..=d).rev().step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(k)] = x;
+            if !is_odd && (k - delta).abs() <= d && x + forward[at(delta - k)] >= n {
+                return ((n - x) as usize, (m - y) as usize);
+            }
+        }
+    }
+    unreachable!("the paths from both ends always meet")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_texts() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb", "old", "new", 3), "");
+    }
+
+    #[test]
+    fn changed_lines_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\n";
+        assert_eq!(
+            unified_diff(old, new, "old", "new", 1),
+            "--- old\n+++ new\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -8 +8,2 @@\n h\n+i\n"
+        );
+        assert_eq!(
+            unified_diff(old, new, "old", "new", 3),
+            "--- old\n+++ new\n@@ -1,8 +1,9 @@\n a\n-b\n+B\n c\n d\n e\n f\n g\n h\n+i\n"
+        );
+    }
+
+    #[test]
+    fn empty_sides() {
+        assert_eq!(
+            unified_diff("", "a\nb\n", "old", "new", 3),
+            "--- old\n+++ new\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+        assert_eq!(
+            unified_diff("a\n", "", "old", "new", 3),
+            "--- old\n+++ new\n@@ -1 +0,0 @@\n-a\n"
+        );
+    }
+
+    #[test]
+    fn minimal_edit_scripts() {
+        // Compares the number of edits with the length of the longest common subsequence
+        let mut seed = 1u32;
+        let mut random_lines = |len: usize| -> Vec<&str> {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    ["a", "b", "c", "d"][(seed >> 16) as usize % 4]
+                })
+                .collect()
+        };
+        for i in 0..200 {
+            let old = random_lines(i % 13);
+            let new = random_lines(i % 7 + i % 5);
+
+            let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+            for x in (0..old.len()).rev() {
+                for y in (0..new.len()).rev() {
+                    lcs[x][y] = if old[x] == new[y] {
+                        lcs[x + 1][y + 1] + 1
+                    } else {
+                        lcs[x + 1][y].max(lcs[x][y + 1])
+                    };
+                }
+            }
+            let edits = shortest_edit_script(&old, &new);
+            let count = |kind| edits.iter().filter(|&&edit| edit == kind).count();
+            assert_eq!(count(Edit::Keep), lcs[0][0], "{old:?} -> {new:?}");
+            assert_eq!(count(Edit::Keep) + count(Edit::Delete), old.len());
+            assert_eq!(count(Edit::Keep) + count(Edit::Insert), new.len());
+        }
+    }
+
+    #[test]
+    fn large_mostly_changed_texts() {
+        // Keeping every step of the search would take hundreds of megabytes here
+        let lines = |prefix: &str| -> String {
+            (0..2000)
+                .map(|i| {
+                    if i % 100 == 0 {
+                        format!("same {i}\n")
+                    } else {
+                        format!("{prefix} {i}\n")
+                    }
+                })
+                .collect()
+        };
+        let diff = unified_diff(&lines("old"), &lines("new"), "old", "new", 0);
+        assert_eq!(
+            diff.lines().filter(|line| line.starts_with('-')).count(),
+            1981
+        );
+        assert_eq!(
+            diff.lines().filter(|line| line.starts_with('+')).count(),
+            1981
+        );
+        assert!(diff.contains("@@ -2,99 +2,99 @@\n-old 1\n"));
+    }
+}
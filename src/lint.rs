@@ -0,0 +1,294 @@
+/// Kinds of documentation problems detected by [`lint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintKind {
+    MissingSafetyDoc,
+    MissingErrorsDoc,
+    MissingPanicsDoc,
+    EmptyDeprecationNote,
+    BrokenIntraDocLink,
+}
+
+impl LintKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintKind::MissingSafetyDoc => "missing_safety_doc",
+            LintKind::MissingErrorsDoc => "missing_errors_doc",
+            LintKind::MissingPanicsDoc => "missing_panics_doc",
+            LintKind::EmptyDeprecationNote => "empty_deprecation_note",
+            LintKind::BrokenIntraDocLink => "broken_intra_doc_link",
+        }
+    }
+}
+
+/// Source location of an item (lines and columns are 1-based)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub filename: String,
+    pub begin: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl Span {
+    fn from_json(value: nojson::RawJsonValue<'_, '_>) -> Result<Self, nojson::JsonParseError> {
+        let filename = value.to_member("filename")?.required()?.try_into()?;
+        let begin = Self::position_from_json(value.to_member("begin")?.required()?)?;
+        let end = Self::position_from_json(value.to_member("end")?.required()?)?;
+        Ok(Self {
+            filename,
+            begin,
+            end,
+        })
+    }
+
+    fn position_from_json(
+        value: nojson::RawJsonValue<'_, '_>,
+    ) -> Result<(usize, usize), nojson::JsonParseError> {
+        let position: Vec<usize> = value.try_into()?;
+        match position[..] {
+            [line, column] => Ok((line, column)),
+            _ => Err(value.invalid("expected a [line, column] pair")),
+        }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.filename, self.begin.0, self.begin.1)
+    }
+}
+
+impl nojson::DisplayJson for Span {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("filename", &self.filename)?;
+            f.member("begin", [self.begin.0, self.begin.1])?;
+            f.member("end", [self.end.0, self.end.1])
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub lint: LintKind,
+    pub path: crate::doc::ItemPath,
+    pub kind: crate::doc::ItemKind,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// Checks the public items of a crate for common documentation problems
+pub fn lint(doc: &crate::doc::CrateDoc) -> crate::Result<Vec<Finding>> {
+    let paths = doc.json().value().to_member("paths")?.get();
+    let mut findings = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    for (path, item) in &doc.show_items {
+        if !visited.insert(item.id) {
+            continue;
+        }
+        let Some(value) = doc.items.get_by_id(doc.json(), item.id) else {
+            continue;
+        };
+        let span = match value.to_member("span")?.get() {
+            Some(span) if !span.kind().is_null() => Some(Span::from_json(span)?),
+            _ => None,
+        };
+        let mut push = |lint, message: String| {
+            findings.push(Finding {
+                lint,
+                path: path.clone(),
+                kind: item.kind,
+                message,
+                span: span.clone(),
+            });
+        };
+
+        let docs = item.docs(doc.json())?.unwrap_or_default();
+        if item.kind == crate::doc::ItemKind::Function {
+            let inner = item.inner(doc.json());
+            let is_unsafe: bool = inner
+                .to_member("header")?
+                .required()?
+                .to_member("is_unsafe")?
+                .required()?
+                .try_into()?;
+            if is_unsafe && !has_section(&docs, "Safety") {
+                push(
+                    LintKind::MissingSafetyDoc,
+                    "unsafe function has no `# Safety` section".to_owned(),
+                );
+            }
+            if returns_result(inner)? && !has_section(&docs, "Errors") {
+                push(
+                    LintKind::MissingErrorsDoc,
+                    "function returning `Result` has no `# Errors` section".to_owned(),
+                );
+            }
+            if (has_track_caller_attr(value)? || mentions_panic(&docs))
+                && !has_section(&docs, "Panics")
+            {
+                push(
+                    LintKind::MissingPanicsDoc,
+                    "function that can panic has no `# Panics` section".to_owned(),
+                );
+            }
+        }
+
+        if let Some(note) = item.deprecation_note(doc.json())?
+            && note.trim().is_empty()
+        {
+            push(
+                LintKind::EmptyDeprecationNote,
+                "deprecated item has no note".to_owned(),
+            );
+        }
+
+        if let Some(links) = value.to_member("links")?.get() {
+            for (text, id) in links.to_object()? {
+                let is_known = doc.items.get_by_id(doc.json(), id.try_into()?).is_some()
+                    || match paths {
+                        Some(paths) => paths.to_member(id.as_raw_str())?.get().is_some(),
+                        None => false,
+                    };
+                if !is_known {
+                    push(
+                        LintKind::BrokenIntraDocLink,
+                        format!(
+                            "intra-doc link {} points to id {}, which is in neither `index` nor `paths`",
+                            text.as_raw_str(),
+                            id.as_raw_str()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn returns_result(inner: nojson::RawJsonValue<'_, '_>) -> crate::Result<bool> {
+    let output = inner
+        .to_member("sig")?
+        .required()?
+        .to_member("output")?
+        .required()?;
+    if output.kind().is_null() {
+        return Ok(false);
+    }
+    let Some(resolved) = output.to_member("resolved_path")?.get() else {
+        return Ok(false);
+    };
+    let path = resolved
+        .to_member("path")?
+        .required()?
+        .to_unquoted_string_str()?;
+    // e.g., `Result`, `io::Result` and `std::result::Result`
+    Ok(path.rsplit("::").next() == Some("Result"))
+}
+
+fn has_track_caller_attr(value: nojson::RawJsonValue<'_, '_>) -> crate::Result<bool> {
+    let Some(attrs) = value.to_member("attrs")?.get() else {
+        return Ok(false);
+    };
+    for attr in attrs.to_array()? {
+        // e.g., `{"other": "#[attr = TrackCaller]"}` (`{"other": "#[track_caller]"}` in older formats)
+        if attr.kind().is_string() {
+            continue;
+        }
+        let Some(other) = attr.to_member("other")?.get() else {
+            continue;
+        };
+        let text = other.to_unquoted_string_str()?;
+        if text.contains("track_caller") || text.contains("TrackCaller") {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns whether `docs` has a Markdown heading (of any level) with the given title
+fn has_section(docs: &str, title: &str) -> bool {
+    crate::markdown::prose_lines(docs).any(|(_, line)| {
+        let line = line.trim();
+        let heading = line.trim_start_matches('#');
+        heading.len() < line.len()
+            && heading.starts_with(char::is_whitespace)
+            && heading.trim().eq_ignore_ascii_case(title)
+    })
+}
+
+/// Returns whether `docs` states a panic condition such as "panics if" or "will panic when"
+///
+/// Negated statements such as "never panics if" or "does not panic when" do not count.
+fn mentions_panic(docs: &str) -> bool {
+    crate::markdown::prose_lines(docs).any(|(_, line)| {
+        let words: Vec<_> = line
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect();
+        words.windows(2).enumerate().any(|(i, pair)| {
+            matches!(pair[0].as_str(), "panic" | "panics")
+                && matches!(pair[1].as_str(), "if" | "when" | "unless")
+                && !words[i.saturating_sub(2)..i].iter().any(|word| {
+                    matches!(word.as_str(), "never" | "not" | "cannot" | "no")
+                        || word.ends_with("n't")
+                        || word.ends_with("n’t")
+                })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_documentation_problems() -> crate::Result<()> {
+        let text = r##"{"format_version": 57, "root": 0, "paths": {"9": {"crate_id": 1, "path": ["other", "X"], "kind": "struct"}}, "index": {
+            "0": {"id": 0, "name": "t", "visibility": "public", "inner": {"module": {"items": [1, 2, 3, 4, 5]}}, "docs": "See [`f`], [`X`] and [`Y`]", "links": {"`f`": 1, "`X`": 9, "`Y`": 42}, "attrs": [], "deprecation": null, "span": {"filename": "src/lib.rs", "begin": [1, 1], "end": [9, 1]}},
+            "1": {"id": 1, "name": "f", "visibility": "public", "inner": {"function": {"sig": {"inputs": [], "output": {"resolved_path": {"path": "std::io::Result", "id": 9, "args": null}}, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": true, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": "Does it.\n\n```\n# Safety\n```", "links": {}, "attrs": [], "deprecation": null, "span": {"filename": "src/lib.rs", "begin": [3, 5], "end": [3, 20]}},
+            "2": {"id": 2, "name": "g", "visibility": "public", "inner": {"function": {"sig": {"inputs": [], "output": {"resolved_path": {"path": "Result", "id": 9, "args": null}}, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": true, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": "# Safety\n\nOK\n\n## Errors\n\nOn failure", "links": {}, "attrs": [], "deprecation": null, "span": null},
+            "3": {"id": 3, "name": "h", "visibility": "public", "inner": {"function": {"sig": {"inputs": [], "output": null, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": "Panics if empty.", "links": {}, "attrs": [], "deprecation": {"since": null, "note": ""}, "span": null},
+            "4": {"id": 4, "name": "i", "visibility": "public", "inner": {"function": {"sig": {"inputs": [], "output": null, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": null, "links": {}, "attrs": [{"other": "#[attr = TrackCaller]"}], "deprecation": {"since": null, "note": "Use `h`"}, "span": null},
+            "5": {"id": 5, "name": "j", "visibility": "public", "inner": {"function": {"sig": {"inputs": [], "output": null, "is_c_variadic": false}, "generics": {"params": [], "where_predicates": []}, "header": {"is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust"}, "has_body": true}}, "docs": "# Panics\n\nIf empty.", "links": {}, "attrs": [], "deprecation": null, "span": null}
+        }}"##;
+        let doc = crate::doc::CrateDoc::parse(std::path::PathBuf::from(""), text)?;
+        let findings: Vec<_> = lint(&doc)?
+            .into_iter()
+            .map(|x| {
+                let span = x.span.map(|s| s.to_string()).unwrap_or_default();
+                format!("{} {} {span}", x.lint.as_str(), x.path)
+            })
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                "broken_intra_doc_link t src/lib.rs:1:1",
+                "missing_safety_doc t::f src/lib.rs:3:5",
+                "missing_errors_doc t::f src/lib.rs:3:5",
+                "missing_panics_doc t::h ",
+                "empty_deprecation_note t::h ",
+                "missing_panics_doc t::i ",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_panic_conditions() {
+        assert!(mentions_panic("Panics if `index` is out of bounds."));
+        assert!(mentions_panic(
+            "# Examples\n\nThis will panic when the lock is poisoned."
+        ));
+        assert!(mentions_panic("May panic unless `init` was called."));
+
+        assert!(!mentions_panic("Never panics if the input is valid UTF-8."));
+        assert!(!mentions_panic(
+            "This function does not panic when the buffer is full."
+        ));
+        assert!(!mentions_panic("Unlike `f`, this doesn't panic if empty."));
+        assert!(!mentions_panic("Won’t panic when called twice."));
+        assert!(!mentions_panic("Returns `None` instead of a panic."));
+        assert!(!mentions_panic("```\nassert!(x); // panics if false\n```"));
+    }
+}
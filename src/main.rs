@@ -17,7 +17,12 @@ fn main() -> noargs::Result<()> {
         .is_present();
 
     if ext {
-        let _ = siru::command_build_doc::try_run(&mut args)?;
+        let _ = siru::command_build_doc::try_run(&mut args)?
+            || siru::command_api_diff::try_run(&mut args)?
+            || siru::command_api_snapshot::try_run(&mut args)?
+            || siru::command_cache::try_run(&mut args)?
+            || siru::command_coverage::try_run(&mut args)?
+            || siru::command_lint::try_run(&mut args)?;
     } else {
         siru::command_main::run(&mut args)?;
     }
@@ -1,38 +1,72 @@
-pub fn add_rust_to_code_blocks(text: &str) -> String {
+/// Normalizes the fenced code blocks in documentation text for Markdown viewers
+///
+/// Blocks that rustdoc treats as Rust code (bare fences and fences with only rustdoc
+/// attributes such as `no_run` or `edition2021`) get the `rust` info string, and their other
+/// attributes are kept as a leading comment. Lines hidden by rustdoc (`# ...`) are removed
+/// unless `keep_hidden_lines` is `true`, in which case they are shown without the `# ` marker.
+pub fn normalize_code_blocks(text: &str, keep_hidden_lines: bool) -> String {
     let mut result = String::new();
-    let lines: Vec<&str> = text.lines().collect();
-    let mut i = 0;
+    let mut lines = text.lines();
 
-    while i < lines.len() {
-        let line = lines[i];
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let Some(fence) = code_fence(trimmed) else {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        };
 
-        if let Some(rest) = line.strip_prefix("```") {
-            if rest.is_empty() || rest.chars().all(|c| c.is_whitespace()) {
-                result.push_str("```rust\n");
-            } else {
+        let info = trimmed[fence.len()..].trim();
+        let attributes: Vec<&str> = info
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|x| !x.is_empty())
+            .collect();
+        let is_rust =
+            attributes.contains(&"rust") || attributes.iter().all(|x| is_rustdoc_attribute(x));
+        if is_rust {
+            result.push_str(&format!("{indent}{fence}rust\n"));
+            let notes: Vec<_> = attributes.into_iter().filter(|x| *x != "rust").collect();
+            if !notes.is_empty() {
+                result.push_str(&format!("{indent}// {}\n", notes.join(", ")));
+            }
+        } else {
+            result.push_str(line);
+            result.push('\n');
+        }
+
+        for line in lines.by_ref() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with(fence) && trimmed[fence.len()..].trim().is_empty() {
                 result.push_str(line);
                 result.push('\n');
+                break;
             }
 
-            i += 1;
-
-            while i < lines.len() {
-                let current_line = lines[i];
-                if current_line.starts_with("```") {
-                    result.push_str(current_line);
-                    result.push('\n');
-                    i += 1;
-                    break;
-                } else {
-                    result.push_str(current_line);
-                    result.push('\n');
-                    i += 1;
+            if !is_rust {
+                result.push_str(line);
+                result.push('\n');
+                continue;
+            }
+            // Same rules as rustdoc: `# code` and `#` are hidden, `##` escapes a leading `#`
+            let hidden = if trimmed.starts_with("##") {
+                None
+            } else if trimmed == "#" {
+                Some("")
+            } else {
+                trimmed.strip_prefix("# ")
+            };
+            match hidden {
+                None if trimmed.starts_with("##") => result.push_str(&line.replacen("##", "#", 1)),
+                None => result.push_str(line),
+                Some(hidden) if keep_hidden_lines => {
+                    let content = line.strip_prefix(indent).unwrap_or(trimmed);
+                    let content_indent = &content[..content.len() - trimmed.len()];
+                    result.push_str(&format!("{indent}{content_indent}{hidden}"));
                 }
+                Some(_) => continue,
             }
-        } else {
-            result.push_str(line);
             result.push('\n');
-            i += 1;
         }
     }
 
@@ -43,6 +77,31 @@ pub fn add_rust_to_code_blocks(text: &str) -> String {
     result
 }
 
+/// Returns the opening fence (e.g., "```" or "~~~~") at the start of a line
+fn code_fence(line: &str) -> Option<&str> {
+    let c = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|x| *x == c).count();
+    (len >= 3).then(|| &line[..len])
+}
+
+/// Returns whether a code block attribute is one that rustdoc accepts for Rust code blocks
+fn is_rustdoc_attribute(attribute: &str) -> bool {
+    matches!(
+        attribute,
+        "rust"
+            | "ignore"
+            | "should_panic"
+            | "no_run"
+            | "compile_fail"
+            | "test_harness"
+            | "standalone_crate"
+    ) || attribute.starts_with("ignore-")
+        || attribute.starts_with("edition")
+        || (attribute.len() == 5
+            && attribute.starts_with('E')
+            && attribute[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
 pub fn increase_heading_levels(text: &str) -> String {
     let mut result = String::new();
     let mut in_code_block = false;
@@ -73,3 +132,346 @@ pub fn increase_heading_levels(text: &str) -> String {
 
     result
 }
+
+/// Destination of a documentation link resolved by the caller of [`rewrite_links`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkTarget {
+    /// Canonical path of the linked item (e.g., `my_crate::module::Item`)
+    pub path: String,
+    /// Anchor of the linked item's heading if it is part of the same output
+    pub anchor: Option<String>,
+}
+
+/// Rewrites the links in `text` whose destinations are resolved by `resolve`
+///
+/// Inline (`[text](dest)`), reference (`[text][label]`) and shortcut (`[text]`) links are
+/// supported. The destinations passed to `resolve` are as written in `text` (e.g., `` `Foo` ``
+/// for a shortcut link ``[`Foo`]``), which is also how rustdoc keys its `links` map.
+/// Resolved links point to their anchors if any, or are replaced by their plain paths otherwise.
+/// Unresolved links, code spans and code blocks are left as they are.
+pub fn rewrite_links<F>(text: &str, mut resolve: F) -> String
+where
+    F: FnMut(&str) -> Option<LinkTarget>,
+{
+    let mut definitions = std::collections::HashMap::new();
+    let mut resolved_definition_lines = std::collections::HashSet::new();
+    for (i, line) in prose_lines(text) {
+        if let Some((label, dest)) = parse_link_definition(line) {
+            if resolve(dest).is_some() {
+                resolved_definition_lines.insert(i);
+            }
+            definitions.entry(label.to_lowercase()).or_insert(dest);
+        }
+    }
+
+    let prose: std::collections::HashSet<_> = prose_lines(text).map(|(i, _)| i).collect();
+    let mut result = String::new();
+    for (i, line) in text.lines().enumerate() {
+        if resolved_definition_lines.contains(&i) {
+            continue;
+        }
+        if prose.contains(&i) {
+            result.push_str(&rewrite_line_links(line, &definitions, &mut resolve));
+        } else {
+            result.push_str(line);
+        }
+        result.push('\n');
+    }
+
+    if !text.ends_with('\n') && result.ends_with('\n') {
+        result.pop();
+    }
+
+    result
+}
+
+/// Returns the GitHub-style anchor of a heading (e.g., `fn-my_cratefoo` for ``[fn] `my_crate::foo` ``)
+pub fn heading_anchor(heading: &str) -> String {
+    heading
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c.to_lowercase().next().unwrap_or(c))
+            } else if c == ' ' {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the lines outside fenced code blocks along with their indices
+pub fn prose_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut fence: Option<&str> = None;
+    text.lines().enumerate().filter(move |(_, line)| {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                false
+            }
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                false
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    })
+}
+
+/// Parses a link reference definition line (`[label]: dest`)
+fn parse_link_definition(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let end = find_closing_bracket(rest, '[', ']')?;
+    let label = &rest[..end];
+    let dest = rest[end + 1..].strip_prefix(':')?.trim();
+    // Titles (`[label]: dest "title"`) are not supported
+    (!dest.is_empty() && !dest.contains(char::is_whitespace)).then_some((label, dest))
+}
+
+fn rewrite_line_links<F>(
+    line: &str,
+    definitions: &std::collections::HashMap<String, &str>,
+    resolve: &mut F,
+) -> String
+where
+    F: FnMut(&str) -> Option<LinkTarget>,
+{
+    let mut result = String::new();
+    let mut i = 0;
+    while let Some(c) = line[i..].chars().next() {
+        let rest = &line[i..];
+        if c == '`' {
+            let span_len = code_span_len(rest).unwrap_or(rest.len());
+            result.push_str(&rest[..span_len]);
+            i += span_len;
+            continue;
+        }
+
+        if c == '['
+            && !result.ends_with('!')
+            && let Some((link_len, link_text, target)) = parse_link(rest, definitions, resolve)
+        {
+            result.push_str(&format_link(link_text, &target));
+            i += link_len;
+            continue;
+        }
+
+        result.push(c);
+        i += c.len_utf8();
+    }
+    result
+}
+
+/// Parses a link at the start of `text` and resolves it, returning its length, text and target
+fn parse_link<'a, F>(
+    text: &'a str,
+    definitions: &std::collections::HashMap<String, &str>,
+    resolve: &mut F,
+) -> Option<(usize, &'a str, LinkTarget)>
+where
+    F: FnMut(&str) -> Option<LinkTarget>,
+{
+    let text_end = 1 + find_closing_bracket(&text[1..], '[', ']')?;
+    let link_text = &text[1..text_end];
+    let rest = &text[text_end + 1..];
+
+    if let Some(inner) = rest.strip_prefix('(') {
+        let dest_end = find_closing_bracket(inner, '(', ')')?;
+        let target = resolve(inner[..dest_end].trim())?;
+        return Some((text_end + 2 + dest_end + 1, link_text, target));
+    }
+
+    if let Some(inner) = rest.strip_prefix('[')
+        && let Some(label_end) = find_closing_bracket(inner, '[', ']')
+    {
+        let label = if label_end == 0 {
+            link_text
+        } else {
+            &inner[..label_end]
+        };
+        let dest = definitions.get(&label.to_lowercase())?;
+        let target = resolve(dest)?;
+        return Some((text_end + 2 + label_end + 1, link_text, target));
+    }
+
+    let dest = definitions
+        .get(&link_text.to_lowercase())
+        .copied()
+        .unwrap_or(link_text);
+    let target = resolve(dest)?;
+    Some((text_end + 1, link_text, target))
+}
+
+fn format_link(link_text: &str, target: &LinkTarget) -> String {
+    if let Some(anchor) = &target.anchor {
+        return format!("[{link_text}](#{anchor})");
+    }
+
+    let path = format!("`{}`", target.path);
+    let is_code_only = link_text.starts_with('`')
+        && link_text.ends_with('`')
+        && code_span_len(link_text) == Some(link_text.len());
+    if is_code_only {
+        path
+    } else {
+        format!("{link_text} ({path})")
+    }
+}
+
+/// Returns the byte offset of the bracket closing an already opened one (code spans are skipped)
+fn find_closing_bracket(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        if c == '`' {
+            i += code_span_len(&text[i..])?;
+            continue;
+        }
+        if c == '\\' {
+            i += 1;
+            i += text[i..].chars().next().map_or(0, char::len_utf8);
+            continue;
+        }
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some(i);
+            }
+            depth -= 1;
+        }
+        i += c.len_utf8();
+    }
+    None
+}
+
+/// Returns the length of the code span starting at the beginning of `text`
+fn code_span_len(text: &str) -> Option<usize> {
+    let ticks = text.chars().take_while(|c| *c == '`').count();
+    let fence = &text[..ticks];
+    let mut offset = ticks;
+    while let Some(pos) = text[offset..].find(fence) {
+        let start = offset + pos;
+        let run = text[start..].chars().take_while(|c| *c == '`').count();
+        if run == ticks {
+            return Some(start + ticks);
+        }
+        offset = start + run;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(dest: &str) -> Option<LinkTarget> {
+        match dest {
+            "`Foo`" | "Foo" | "struct.Foo.html" => Some(LinkTarget {
+                path: "my_crate::Foo".to_owned(),
+                anchor: Some(heading_anchor("[struct] `my_crate::Foo`")),
+            }),
+            "`Bar::baz`" | "crate::Bar::baz" => Some(LinkTarget {
+                path: "my_crate::Bar::baz".to_owned(),
+                anchor: None,
+            }),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn normalize_rust_code_blocks() {
+        let text = concat!(
+            "```\n",
+            "# use foo::Bar;\n",
+            "#\n",
+            "let x = Bar;\n",
+            "## not hidden\n",
+            "```\n",
+            "\n",
+            "```no_run,edition2021\n",
+            "    # fn main() {\n",
+            "    run();\n",
+            "    # }\n",
+            "```\n",
+            "\n",
+            "```text\n",
+            "# heading\n",
+            "```"
+        );
+        assert_eq!(
+            normalize_code_blocks(text, false),
+            concat!(
+                "```rust\n",
+                "let x = Bar;\n",
+                "# not hidden\n",
+                "```\n",
+                "\n",
+                "```rust\n",
+                "// no_run, edition2021\n",
+                "    run();\n",
+                "```\n",
+                "\n",
+                "```text\n",
+                "# heading\n",
+                "```"
+            )
+        );
+        assert_eq!(
+            normalize_code_blocks(text, true),
+            concat!(
+                "```rust\n",
+                "use foo::Bar;\n",
+                "\n",
+                "let x = Bar;\n",
+                "# not hidden\n",
+                "```\n",
+                "\n",
+                "```rust\n",
+                "// no_run, edition2021\n",
+                "    fn main() {\n",
+                "    run();\n",
+                "    }\n",
+                "```\n",
+                "\n",
+                "```text\n",
+                "# heading\n",
+                "```"
+            )
+        );
+    }
+
+    #[test]
+    fn rewrite_resolved_links() {
+        let text = concat!(
+            "See [`Foo`], [`Bar::baz`] and [the method](crate::Bar::baz).\n",
+            "Also [Foo](struct.Foo.html), [`Unknown`] and [docs](https://example.com).\n",
+            "Reference [baz][b] and `[`Foo`]` in code.\n",
+            "\n",
+            "[b]: crate::Bar::baz\n",
+            "\n",
+            "```\n",
+            "let x = [`Foo`];\n",
+            "```"
+        );
+        assert_eq!(
+            rewrite_links(text, resolve),
+            concat!(
+                "See [`Foo`](#struct-my_cratefoo), `my_crate::Bar::baz` and the method (`my_crate::Bar::baz`).\n",
+                "Also [Foo](#struct-my_cratefoo), [`Unknown`] and [docs](https://example.com).\n",
+                "Reference baz (`my_crate::Bar::baz`) and `[`Foo`]` in code.\n",
+                "\n",
+                "\n",
+                "```\n",
+                "let x = [`Foo`];\n",
+                "```"
+            )
+        );
+    }
+}
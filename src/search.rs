@@ -0,0 +1,837 @@
+/// Scores item paths against the `ITEM_PATH_PART` arguments
+///
+/// Matches in the item name rank higher than matches in parent path segments,
+/// and within each, exact > prefix > word boundary > substring > fuzzy matches.
+#[derive(Debug)]
+pub struct PathMatcher {
+    parts: Vec<String>,
+    substring_only: bool,
+}
+
+impl PathMatcher {
+    pub fn new(parts: Vec<String>, substring_only: bool) -> Self {
+        Self {
+            parts,
+            substring_only,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns `true` if matches only filter items and are not ranked
+    pub fn is_substring_only(&self) -> bool {
+        self.substring_only
+    }
+
+    /// Returns `None` if any part does not match the path, otherwise a score (higher is better)
+    pub fn score(&self, path: &crate::doc::ItemPath) -> Option<u32> {
+        if self.substring_only {
+            let path = path.to_string();
+            return self
+                .parts
+                .iter()
+                .all(|part| path.contains(part.as_str()))
+                .then_some(0);
+        }
+
+        let segments = path.segments();
+        let (name, parents) = segments.split_last()?;
+        let mut total = 0;
+        for part in &self.parts {
+            let score = if part.contains("::") {
+                score_multi_segment(segments, part)
+                    .or_else(|| path.to_string().contains(part.as_str()).then_some(30))?
+            } else {
+                // Subsequence matches against parent segments are too noisy to be useful
+                score_segment(name, part, true)
+                    .map(|score| score * 2)
+                    .into_iter()
+                    .chain(parents.iter().filter_map(|s| score_segment(s, part, false)))
+                    .max()?
+            };
+            total += score;
+        }
+        Some(total)
+    }
+}
+
+/// Scores a part such as `sync::Mutex` against consecutive path segments
+fn score_multi_segment(segments: &[String], part: &str) -> Option<u32> {
+    let sub_parts: Vec<_> = part.split("::").filter(|x| !x.is_empty()).collect();
+    if sub_parts.is_empty() || sub_parts.len() > segments.len() {
+        return None;
+    }
+    (0..=segments.len() - sub_parts.len())
+        .filter_map(|start| {
+            let mut total = 0;
+            for (i, sub_part) in sub_parts.iter().enumerate() {
+                let is_name = start + i == segments.len() - 1;
+                let score = score_segment(&segments[start + i], sub_part, is_name)?;
+                total += if is_name { score * 2 } else { score };
+            }
+            Some(total)
+        })
+        .max()
+}
+
+fn score_segment(segment: &str, part: &str, allow_subsequence: bool) -> Option<u32> {
+    let lower_segment = segment.to_lowercase();
+    let lower_part = part.to_lowercase();
+
+    if segment == part {
+        Some(50)
+    } else if lower_segment == lower_part {
+        Some(45)
+    } else if lower_segment.starts_with(&lower_part) {
+        Some(40)
+    } else if word_boundaries(segment).any(|i| lower_segment[i..].starts_with(&lower_part)) {
+        Some(35)
+    } else if lower_segment.contains(&lower_part) {
+        Some(30)
+    } else if allow_subsequence && is_subsequence(&lower_segment, &lower_part) {
+        Some(10)
+    } else if lower_part.len() >= 3
+        && edit_distance(&lower_segment, &lower_part) <= (lower_part.len() / 3).max(1)
+    {
+        Some(8)
+    } else {
+        None
+    }
+}
+
+/// Returns the byte offsets where words start in `CamelCase` or `snake_case` identifiers
+fn word_boundaries(segment: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut prev: Option<char> = None;
+    segment.char_indices().filter_map(move |(i, c)| {
+        let is_boundary = match prev {
+            None => true,
+            Some(p) => p == '_' || (c.is_uppercase() && !p.is_uppercase()),
+        };
+        prev = Some(c);
+        (is_boundary && c != '_').then_some(i)
+    })
+}
+
+fn is_subsequence(text: &str, pattern: &str) -> bool {
+    let mut text_chars = text.chars();
+    pattern.chars().all(|p| text_chars.any(|t| t == p))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            row[j + 1] = (prev_row[j] + cost)
+                .min(prev_row[j + 1] + 1)
+                .min(row[j] + 1);
+        }
+        prev_row = row;
+    }
+    prev_row[b.len()]
+}
+
+/// Matches function signatures against a simplified query such as `&str -> Result<_, _>`
+///
+/// Inputs are matched in any order, and generic parameters, references and `Self`
+/// match loosely so that approximate queries still find candidates.
+#[derive(Debug)]
+pub struct SignatureMatcher {
+    inputs: Vec<SigType>,
+    output: Option<SigType>,
+}
+
+impl std::str::FromStr for SignatureMatcher {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = SigParser::new(s);
+        let mut inputs = Vec::new();
+        parser.skip_whitespace();
+        if !parser.peek_str("->") && !parser.is_eof() {
+            inputs.push(parser.parse_type()?);
+            while parser.eat(",") {
+                inputs.push(parser.parse_type()?);
+            }
+        }
+        let output = if parser.eat("->") {
+            Some(parser.parse_type()?)
+        } else {
+            None
+        };
+        if !parser.is_eof() {
+            return Err(format!(
+                "invalid signature query {s:?}: unexpected {:?}",
+                parser.rest()
+            ));
+        }
+        Ok(Self { inputs, output })
+    }
+}
+
+impl SignatureMatcher {
+    /// Returns `None` if `item` is not a function matching the query, otherwise a score (higher is better)
+    ///
+    /// Signatures restored from [`crate::cache`] are used if any, so that the JSON of cached
+    /// docs need not be loaded.
+    pub fn score(
+        &self,
+        doc: &crate::doc::CrateDoc,
+        item: &crate::doc::Item,
+    ) -> crate::Result<Option<u32>> {
+        if item.kind != crate::doc::ItemKind::Function {
+            return Ok(None);
+        }
+        if let Some(function_sigs) = &doc.function_sigs {
+            return Ok(function_sigs
+                .get(&item.id)
+                .and_then(|sig| self.score_signature(sig)));
+        }
+        let sig = FunctionSig::from_json(item.inner(doc.json()))?;
+        Ok(self.score_signature(&sig))
+    }
+
+    fn score_signature(&self, sig: &FunctionSig) -> Option<u32> {
+        let inputs = &sig.inputs;
+        let input_score = best_assignment(&self.inputs, inputs, &mut vec![false; inputs.len()])?;
+        let output_score = match &self.output {
+            Some(query) => query.score(&sig.output)?,
+            None => 0,
+        };
+
+        // Each parameter not mentioned in the query makes the match less relevant
+        // (but functions with many parameters still match)
+        let extra_inputs = (inputs.len() - self.inputs.len()) as u32;
+        Some((input_score + output_score + 1).saturating_sub(extra_inputs * 3))
+    }
+}
+
+/// Parameter and return types of a function in the simplified model of signature queries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSig {
+    inputs: Vec<SigType>,
+    /// `()` if the function returns nothing
+    output: SigType,
+}
+
+impl FunctionSig {
+    /// Reads the signature of a function item (`inner` is the content of its `function` member)
+    pub fn from_json(inner: nojson::RawJsonValue) -> crate::Result<Self> {
+        let sig = inner.to_member("sig")?.required()?;
+        let mut inputs = Vec::new();
+        for input_pair in sig.to_member("inputs")?.required()?.to_array()? {
+            if let Some(ty) = input_pair.to_array()?.nth(1) {
+                inputs.push(SigType::from_json(ty)?);
+            }
+        }
+        let output = match sig.to_member("output")?.get() {
+            Some(ty) if !ty.kind().is_null() => SigType::from_json(ty)?,
+            _ => SigType::Tuple(Vec::new()),
+        };
+        Ok(Self { inputs, output })
+    }
+
+    /// Reads the signatures of the functions among the public items of `doc`
+    pub fn collect(
+        doc: &crate::doc::CrateDoc,
+    ) -> crate::Result<std::collections::HashMap<crate::doc::ItemId, Self>> {
+        let mut function_sigs = std::collections::HashMap::new();
+        for (_, item) in &doc.show_items {
+            if item.kind == crate::doc::ItemKind::Function {
+                function_sigs.insert(item.id, Self::from_json(item.inner(doc.json()))?);
+            }
+        }
+        Ok(function_sigs)
+    }
+}
+
+impl nojson::DisplayJson for FunctionSig {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        f.object(|f| {
+            f.member("inputs", &self.inputs)?;
+            f.member("output", &self.output)
+        })
+    }
+}
+
+impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for FunctionSig {
+    type Error = nojson::JsonParseError;
+
+    fn try_from(value: nojson::RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            inputs: value.to_member("inputs")?.required()?.try_into()?,
+            output: value.to_member("output")?.required()?.try_into()?,
+        })
+    }
+}
+
+/// Returns the best total score of matching every query input with a distinct function input
+fn best_assignment(queries: &[SigType], inputs: &[SigType], used: &mut [bool]) -> Option<u32> {
+    let Some((query, rest)) = queries.split_first() else {
+        return Some(0);
+    };
+
+    let mut best = None;
+    for (i, input) in inputs.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        let Some(score) = query.score(input) else {
+            continue;
+        };
+        used[i] = true;
+        if let Some(rest_score) = best_assignment(rest, inputs, used) {
+            best = best.max(Some(score + rest_score));
+        }
+        used[i] = false;
+    }
+    best
+}
+
+/// Simplified type model shared by signature queries and rustdoc JSON types
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SigType {
+    /// `_` in queries; also types that the model does not distinguish
+    Any,
+    /// Generic parameters, `Self` and associated types such as `<T as Trait>::Item`
+    Generic(String),
+    /// Paths, primitives and `impl Trait` / `dyn Trait` (the name is the last path segment)
+    Named(String, Vec<SigType>),
+    Ref(bool, Box<SigType>),
+    Ptr(bool, Box<SigType>),
+    Slice(Box<SigType>),
+    Tuple(Vec<SigType>),
+}
+
+impl SigType {
+    fn from_json(ty: nojson::RawJsonValue) -> crate::Result<Self> {
+        if let Some(generic) = ty.to_member("generic")?.get() {
+            Ok(Self::Generic(
+                generic.to_unquoted_string_str()?.into_owned(),
+            ))
+        } else if let Some(resolved) = ty.to_member("resolved_path")?.get() {
+            let path = resolved.to_member("path")?.required()?;
+            Self::named_from_json(&path.to_unquoted_string_str()?, resolved)
+        } else if let Some(primitive) = ty.to_member("primitive")?.get() {
+            let name = primitive.to_unquoted_string_str()?.into_owned();
+            Ok(Self::Named(name, Vec::new()))
+        } else if let Some(borrowed_ref) = ty.to_member("borrowed_ref")?.get() {
+            let is_mutable = borrowed_ref
+                .to_member("is_mutable")?
+                .required()?
+                .try_into()?;
+            let inner = Self::from_json(borrowed_ref.to_member("type")?.required()?)?;
+            Ok(Self::Ref(is_mutable, Box::new(inner)))
+        } else if let Some(raw_pointer) = ty.to_member("raw_pointer")?.get() {
+            let is_mutable = raw_pointer
+                .to_member("is_mutable")?
+                .required()?
+                .try_into()?;
+            let inner = Self::from_json(raw_pointer.to_member("type")?.required()?)?;
+            Ok(Self::Ptr(is_mutable, Box::new(inner)))
+        } else if let Some(qualified_path) = ty.to_member("qualified_path")?.get() {
+            let name = qualified_path.to_member("name")?.required()?;
+            Ok(Self::Generic(name.to_unquoted_string_str()?.into_owned()))
+        } else if let Some(tuple) = ty.to_member("tuple")?.get() {
+            let elements = tuple
+                .to_array()?
+                .map(Self::from_json)
+                .collect::<crate::Result<_>>()?;
+            Ok(Self::Tuple(elements))
+        } else if let Some(slice) = ty.to_member("slice")?.get() {
+            Ok(Self::Slice(Box::new(Self::from_json(slice)?)))
+        } else if let Some(array) = ty.to_member("array")?.get() {
+            let inner = Self::from_json(array.to_member("type")?.required()?)?;
+            Ok(Self::Slice(Box::new(inner)))
+        } else if let Some(dyn_trait) = ty.to_member("dyn_trait")?.get() {
+            let traits = dyn_trait.to_member("traits")?.required()?;
+            match traits.to_array()?.next() {
+                Some(poly_trait) => {
+                    let trait_path = poly_trait.to_member("trait")?.required()?;
+                    let path = trait_path.to_member("path")?.required()?;
+                    Self::named_from_json(&path.to_unquoted_string_str()?, trait_path)
+                }
+                None => Ok(Self::Any),
+            }
+        } else if let Some(impl_trait) = ty.to_member("impl_trait")?.get() {
+            for bound in impl_trait.to_array()? {
+                if let Some(trait_bound) = bound.to_member("trait_bound")?.get() {
+                    let trait_path = trait_bound.to_member("trait")?.required()?;
+                    let path = trait_path.to_member("path")?.required()?;
+                    return Self::named_from_json(&path.to_unquoted_string_str()?, trait_path);
+                }
+            }
+            Ok(Self::Any)
+        } else {
+            Ok(Self::Any)
+        }
+    }
+
+    fn named_from_json(path: &str, value: nojson::RawJsonValue) -> crate::Result<Self> {
+        let name = path.rsplit("::").next().unwrap_or(path).to_owned();
+        let mut args = Vec::new();
+        if let Some(generic_args) = value.to_member("args")?.get()
+            && !generic_args.kind().is_null()
+            && let Some(angle_bracketed) = generic_args.to_member("angle_bracketed")?.get()
+        {
+            for arg in angle_bracketed.to_member("args")?.required()?.to_array()? {
+                if let Some(arg_type) = arg.to_member("type")?.get() {
+                    args.push(Self::from_json(arg_type)?);
+                }
+            }
+        }
+        Ok(Self::Named(name, args))
+    }
+
+    /// Scores how well `actual` (from rustdoc JSON) matches `self` (from the query)
+    fn score(&self, actual: &Self) -> Option<u32> {
+        match (self, actual) {
+            (Self::Any, _) => Some(1),
+            (Self::Generic(a), Self::Generic(b)) => Some(if a == b { 5 } else { 4 }),
+            (Self::Generic(_), _) => Some(1),
+            (_, Self::Generic(_)) | (_, Self::Any) => Some(2),
+            (Self::Named(a, a_args), Self::Named(b, b_args)) => {
+                if a != b {
+                    return None;
+                }
+                let mut score = 6;
+                for (a, b) in a_args.iter().zip(b_args) {
+                    score += a.score(b)?;
+                }
+                // Aliases may take fewer args (e.g., `io::Result<T>` for `Result<_, _>`)
+                let extra_args = a_args.len().saturating_sub(b_args.len()) as u32;
+                Some(score.saturating_sub(extra_args * 3))
+            }
+            (Self::Ref(a_mut, a), Self::Ref(b_mut, b))
+            | (Self::Ptr(a_mut, a), Self::Ptr(b_mut, b)) => {
+                let bonus = if a_mut == b_mut { 2 } else { 0 };
+                Some(a.score(b)? + bonus)
+            }
+            // References match loosely in both directions (e.g., `String` vs `&String`)
+            (Self::Ref(_, a), b) => a.score(b),
+            (a, Self::Ref(_, b)) => a.score(b),
+            (Self::Slice(a), Self::Slice(b)) => Some(a.score(b)? + 2),
+            (Self::Tuple(a), Self::Tuple(b)) if a.len() == b.len() => {
+                let mut score = 2;
+                for (a, b) in a.iter().zip(b) {
+                    score += a.score(b)?;
+                }
+                Some(score)
+            }
+            _ => None,
+        }
+    }
+
+    /// Generic parameter names in queries are single uppercase letters (optionally followed by digits)
+    fn is_generic_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars.next().is_some_and(|c| c.is_ascii_uppercase()) && chars.all(|c| c.is_ascii_digit())
+    }
+}
+
+impl nojson::DisplayJson for SigType {
+    fn fmt(&self, f: &mut nojson::JsonFormatter<'_, '_>) -> std::fmt::Result {
+        match self {
+            Self::Any => f.value(()),
+            Self::Generic(name) => f.object(|f| f.member("generic", name)),
+            Self::Named(name, args) => f.object(|f| {
+                f.member("named", name)?;
+                f.member("args", args)
+            }),
+            Self::Ref(is_mutable, inner) => f.object(|f| {
+                f.member("ref", inner)?;
+                f.member("is_mutable", is_mutable)
+            }),
+            Self::Ptr(is_mutable, inner) => f.object(|f| {
+                f.member("ptr", inner)?;
+                f.member("is_mutable", is_mutable)
+            }),
+            Self::Slice(inner) => f.object(|f| f.member("slice", inner)),
+            Self::Tuple(elements) => f.object(|f| f.member("tuple", elements)),
+        }
+    }
+}
+
+impl<'text, 'raw> TryFrom<nojson::RawJsonValue<'text, 'raw>> for SigType {
+    type Error = nojson::JsonParseError;
+
+    fn try_from(value: nojson::RawJsonValue<'text, 'raw>) -> Result<Self, Self::Error> {
+        if value.kind().is_null() {
+            return Ok(Self::Any);
+        }
+        let is_mutable = || value.to_member("is_mutable")?.required()?.try_into();
+        if let Some(name) = value.to_member("generic")?.get() {
+            Ok(Self::Generic(name.try_into()?))
+        } else if let Some(name) = value.to_member("named")?.get() {
+            let args = value.to_member("args")?.required()?.try_into()?;
+            Ok(Self::Named(name.try_into()?, args))
+        } else if let Some(inner) = value.to_member("ref")?.get() {
+            Ok(Self::Ref(is_mutable()?, Box::new(inner.try_into()?)))
+        } else if let Some(inner) = value.to_member("ptr")?.get() {
+            Ok(Self::Ptr(is_mutable()?, Box::new(inner.try_into()?)))
+        } else if let Some(inner) = value.to_member("slice")?.get() {
+            Ok(Self::Slice(Box::new(inner.try_into()?)))
+        } else if let Some(elements) = value.to_member("tuple")?.get() {
+            Ok(Self::Tuple(elements.try_into()?))
+        } else {
+            Err(value.invalid("unknown signature type"))
+        }
+    }
+}
+
+struct SigParser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> SigParser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, offset: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn is_eof(&mut self) -> bool {
+        self.skip_whitespace();
+        self.rest().is_empty()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek_str(&mut self, s: &str) -> bool {
+        self.skip_whitespace();
+        self.rest().starts_with(s)
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.peek_str(s) {
+            self.offset += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), String> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(format!("expected {s:?} at {:?}", self.rest()))
+        }
+    }
+
+    fn parse_ident(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':' || c == '\''))
+            .unwrap_or(rest.len());
+        (len > 0).then(|| {
+            self.offset += len;
+            &rest[..len]
+        })
+    }
+
+    fn parse_type(&mut self) -> Result<SigType, String> {
+        if self.eat("&") {
+            // Lifetimes do not take part in matching
+            if self.peek_str("'") {
+                self.parse_ident();
+            }
+            let is_mutable = self.eat_keyword("mut");
+            return Ok(SigType::Ref(is_mutable, Box::new(self.parse_type()?)));
+        }
+        if self.eat("*") {
+            let is_mutable = self.eat_keyword("mut");
+            if !is_mutable && !self.eat_keyword("const") {
+                return Err(format!("expected `const` or `mut` at {:?}", self.rest()));
+            }
+            return Ok(SigType::Ptr(is_mutable, Box::new(self.parse_type()?)));
+        }
+        if self.eat("[") {
+            let inner = self.parse_type()?;
+            if self.eat(";") {
+                self.parse_ident();
+            }
+            self.expect("]")?;
+            return Ok(SigType::Slice(Box::new(inner)));
+        }
+        if self.eat("(") {
+            let mut elements = Vec::new();
+            if !self.eat(")") {
+                elements.push(self.parse_type()?);
+                while self.eat(",") {
+                    if self.peek_str(")") {
+                        break;
+                    }
+                    elements.push(self.parse_type()?);
+                }
+                self.expect(")")?;
+            }
+            return Ok(SigType::Tuple(elements));
+        }
+
+        // `impl Trait` and `dyn Trait` are matched by trait name
+        let _ = self.eat_keyword("impl") || self.eat_keyword("dyn");
+        let Some(path) = self.parse_ident() else {
+            return Err(format!("expected a type at {:?}", self.rest()));
+        };
+        let name = path.rsplit("::").next().unwrap_or(path);
+        if name == "_" {
+            return Ok(SigType::Any);
+        }
+
+        let mut args = Vec::new();
+        if self.eat("<") {
+            loop {
+                // Lifetime arguments do not take part in matching
+                if self.peek_str("'") {
+                    self.parse_ident();
+                } else {
+                    args.push(self.parse_type()?);
+                }
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(">")?;
+        }
+        if args.is_empty() && (name == "Self" || SigType::is_generic_name(name)) {
+            return Ok(SigType::Generic(name.to_owned()));
+        }
+        Ok(SigType::Named(name.to_owned(), args))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.starts_with(keyword)
+            && rest[keyword.len()..].starts_with(|c: char| c.is_whitespace() || c == '&')
+        {
+            self.offset += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_name_matches_first() {
+        let matcher = PathMatcher::new(vec!["map".to_owned()], false);
+        let exact = matcher.score(&path("std::iter::Iterator::map"));
+        let boundary = matcher.score(&path("std::collections::HashMap"));
+        let parent = matcher.score(&path("std::collections::hash_map::Entry"));
+        let fuzzy = matcher.score(&path("std::iter::MapWhile::clamp"));
+        assert!(exact > boundary, "{exact:?} > {boundary:?}");
+        assert!(boundary > parent, "{boundary:?} > {parent:?}");
+        assert!(parent.is_some());
+        assert!(fuzzy.is_some());
+    }
+
+    #[test]
+    fn prefix_ranks_above_boundary() {
+        let matcher = PathMatcher::new(vec!["hash".to_owned()], false);
+        let prefix = matcher.score(&path("std::collections::HashMap"));
+        let boundary = matcher.score(&path("std::collections::BuildHasher"));
+        assert!(prefix > boundary, "{prefix:?} > {boundary:?}");
+    }
+
+    #[test]
+    fn fuzzy_matches() {
+        let matcher = PathMatcher::new(vec!["hsmp".to_owned()], false);
+        assert!(matcher.score(&path("std::collections::HashMap")).is_some());
+
+        let matcher = PathMatcher::new(vec!["hashmpa".to_owned()], false);
+        assert!(matcher.score(&path("std::collections::HashMap")).is_some());
+
+        let matcher = PathMatcher::new(vec!["xyz".to_owned()], false);
+        assert!(matcher.score(&path("std::collections::HashMap")).is_none());
+    }
+
+    #[test]
+    fn multi_segment_parts() {
+        let matcher = PathMatcher::new(vec!["sync::Mutex".to_owned()], false);
+        let exact = matcher.score(&path("std::sync::Mutex"));
+        let prefix = matcher.score(&path("std::sync::MutexGuard"));
+        assert!(exact > prefix, "{exact:?} > {prefix:?}");
+        assert!(prefix.is_some());
+        assert!(matcher.score(&path("std::cell::RefCell")).is_none());
+        assert!(matcher.score(&path("std::sync::mpsc::Sender")).is_none());
+
+        let matcher = PathMatcher::new(vec!["tokio::sync".to_owned()], false);
+        assert!(matcher.score(&path("tokio::sync::Mutex")).is_some());
+
+        // Parts that do not align with segments still match as substrings
+        let matcher = PathMatcher::new(vec!["ync::Mut".to_owned()], false);
+        assert!(matcher.score(&path("std::sync::Mutex")).is_some());
+    }
+
+    #[test]
+    fn substring_only() {
+        let matcher = PathMatcher::new(vec!["Map".to_owned()], true);
+        assert!(matcher.score(&path("std::collections::HashMap")).is_some());
+        assert!(matcher.score(&path("std::iter::Iterator::map")).is_none());
+    }
+
+    #[test]
+    fn parse_signature_query() -> Result<(), String> {
+        let matcher: SignatureMatcher = "&'a mut [u8], T -> Result<Cow<'a, str>, _>".parse()?;
+        assert_eq!(
+            matcher.inputs,
+            [
+                SigType::Ref(true, Box::new(SigType::Slice(Box::new(named("u8"))))),
+                SigType::Generic("T".to_owned()),
+            ]
+        );
+        assert_eq!(
+            matcher.output,
+            Some(SigType::Named(
+                "Result".to_owned(),
+                vec![
+                    SigType::Named("Cow".to_owned(), vec![named("str")]),
+                    SigType::Any
+                ]
+            ))
+        );
+
+        let matcher: SignatureMatcher = "-> ()".parse()?;
+        assert!(matcher.inputs.is_empty());
+        assert_eq!(matcher.output, Some(SigType::Tuple(Vec::new())));
+
+        assert!("Vec<u8".parse::<SignatureMatcher>().is_err());
+        assert!("-> String String".parse::<SignatureMatcher>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn match_signature_types_loosely() -> crate::Result<()> {
+        let string_ref = json_type(
+            r#"{"borrowed_ref":{"lifetime":null,"is_mutable":false,"type":{"resolved_path":{"path":"alloc::string::String","id":1,"args":null}}}}"#,
+        )?;
+        let generic = json_type(r#"{"generic":"K"}"#)?;
+        let option = json_type(
+            r#"{"resolved_path":{"path":"Option","id":2,"args":{"angle_bracketed":{"args":[{"type":{"generic":"Self"}}],"constraints":[]}}}}"#,
+        )?;
+
+        let exact = query_type("&String").score(&string_ref);
+        let loose_ref = query_type("String").score(&string_ref);
+        assert!(exact > loose_ref, "{exact:?} > {loose_ref:?}");
+        assert!(loose_ref.is_some());
+        assert!(query_type("str").score(&string_ref).is_none());
+
+        let generic_match = query_type("T").score(&generic);
+        let concrete_match = query_type("u32").score(&generic);
+        assert!(generic_match > concrete_match);
+        assert!(concrete_match.is_some());
+
+        assert!(query_type("Option<Self>").score(&option) > query_type("Option<_>").score(&option));
+        assert!(query_type("Result<_, _>").score(&option).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn match_paths_with_fewer_generic_args_loosely() -> crate::Result<()> {
+        let io_result = json_type(
+            r#"{"resolved_path":{"path":"std::io::Result","id":1,"args":{"angle_bracketed":{"args":[{"type":{"generic":"T"}}],"constraints":[]}}}}"#,
+        )?;
+        let result = json_type(
+            r#"{"resolved_path":{"path":"Result","id":2,"args":{"angle_bracketed":{"args":[{"type":{"generic":"T"}},{"type":{"generic":"E"}}],"constraints":[]}}}}"#,
+        )?;
+
+        let query = query_type("Result<_, _>");
+        let fewer_args = query.score(&io_result);
+        let same_args = query.score(&result);
+        assert!(same_args > fewer_args, "{same_args:?} > {fewer_args:?}");
+        assert!(fewer_args.is_some());
+        assert!(query_type("Option<_>").score(&io_result).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn match_inputs_in_any_order() -> Result<(), String> {
+        let inputs = [named("usize"), SigType::Ref(false, Box::new(named("str")))];
+        let matcher: SignatureMatcher = "&str, usize".parse()?;
+        let mut used = vec![false; inputs.len()];
+        assert!(best_assignment(&matcher.inputs, &inputs, &mut used).is_some());
+
+        let matcher: SignatureMatcher = "&str, &str".parse()?;
+        let mut used = vec![false; inputs.len()];
+        assert!(best_assignment(&matcher.inputs, &inputs, &mut used).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn match_functions_with_extra_inputs() -> Result<(), String> {
+        let matcher: SignatureMatcher = "&str".parse()?;
+        let str_ref = SigType::Ref(false, Box::new(named("str")));
+        let sig = |inputs: &[SigType]| FunctionSig {
+            inputs: inputs.to_vec(),
+            output: SigType::Tuple(Vec::new()),
+        };
+
+        let exact = matcher.score_signature(&sig(std::slice::from_ref(&str_ref)));
+        let one_extra = matcher.score_signature(&sig(&[str_ref.clone(), named("usize")]));
+        let many_extra = matcher.score_signature(&sig(&[
+            str_ref.clone(),
+            named("usize"),
+            named("usize"),
+            named("bool"),
+            named("char"),
+        ]));
+        assert!(exact > one_extra, "{exact:?} > {one_extra:?}");
+        assert!(one_extra >= many_extra, "{one_extra:?} >= {many_extra:?}");
+        assert!(many_extra.is_some());
+        assert!(matcher.score_signature(&sig(&[named("usize")])).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn function_sig_json_roundtrip() -> crate::Result<()> {
+        let sig = FunctionSig {
+            inputs: vec![
+                SigType::Ref(true, Box::new(SigType::Generic("Self".to_owned()))),
+                SigType::Ptr(false, Box::new(SigType::Slice(Box::new(named("u8"))))),
+                SigType::Any,
+            ],
+            output: SigType::Named(
+                "Result".to_owned(),
+                vec![SigType::Tuple(Vec::new()), named("Error")],
+            ),
+        };
+        let text = nojson::Json(&sig).to_string();
+        let json = nojson::RawJson::parse(&text)?;
+        assert_eq!(FunctionSig::try_from(json.value())?, sig);
+        Ok(())
+    }
+
+    fn named(name: &str) -> SigType {
+        SigType::Named(name.to_owned(), Vec::new())
+    }
+
+    fn query_type(s: &str) -> SigType {
+        SigParser::new(s).parse_type().expect("invalid query")
+    }
+
+    fn json_type(text: &str) -> crate::Result<SigType> {
+        let json = nojson::RawJsonOwned::parse(text)?;
+        SigType::from_json(json.value())
+    }
+
+    fn path(s: &str) -> crate::doc::ItemPath {
+        crate::doc::ItemPath::from_segments(s.split("::").map(str::to_owned).collect())
+    }
+}
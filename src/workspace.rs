@@ -0,0 +1,162 @@
+/// Cargo workspace containing the current directory, as reported by `cargo metadata`
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub root: std::path::PathBuf,
+    /// Target directory (respects `CARGO_TARGET_DIR` and `build.target-dir` of the config)
+    pub target_dir: std::path::PathBuf,
+    pub members: Vec<Package>,
+}
+
+/// A workspace member package
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub name: String,
+    /// Names of the crates of the library (first if any) and binary targets
+    pub crate_names: Vec<String>,
+}
+
+impl Workspace {
+    /// Finds the workspace containing `dir` by running `cargo metadata --no-deps --offline`
+    /// with the nearest `Cargo.toml` found by walking up from `dir`
+    pub fn discover(dir: &std::path::Path) -> Result<Option<Self>, String> {
+        let Some(manifest_path) = dir
+            .ancestors()
+            .map(|dir| dir.join("Cargo.toml"))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+
+        let output = std::process::Command::new("cargo")
+            .args([
+                "metadata",
+                "--no-deps",
+                "--offline",
+                "--format-version",
+                "1",
+            ])
+            .arg("--manifest-path")
+            .arg(&manifest_path)
+            .output()
+            .map_err(|e| format!("failed to run `cargo metadata`: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "`cargo metadata` failed for '{}': {}",
+                manifest_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let text = String::from_utf8(output.stdout)
+            .map_err(|e| format!("invalid `cargo metadata` output: {e}"))?;
+        Self::from_metadata(&text)
+            .map(Some)
+            .map_err(|e| crate::json::format_parse_error(&text, &e))
+    }
+
+    /// Reads the output of `cargo metadata --format-version 1`
+    pub fn from_metadata(text: &str) -> Result<Self, nojson::JsonParseError> {
+        let json = nojson::RawJson::parse(text)?;
+        let value = json.value();
+        let member_ids: Vec<String> = value
+            .to_member("workspace_members")?
+            .required()?
+            .try_into()?;
+
+        let mut members = Vec::new();
+        for package in value.to_member("packages")?.required()?.to_array()? {
+            let id: String = package.to_member("id")?.required()?.try_into()?;
+            let Some(order) = member_ids.iter().position(|x| *x == id) else {
+                continue;
+            };
+
+            let mut lib_names = Vec::new();
+            let mut bin_names = Vec::new();
+            for target in package.to_member("targets")?.required()?.to_array()? {
+                let name: String = target.to_member("name")?.required()?.try_into()?;
+                let kinds: Vec<String> = target.to_member("kind")?.required()?.try_into()?;
+                let crate_name = name.replace('-', "_");
+                if kinds.iter().any(|kind| kind == "bin") {
+                    bin_names.push(crate_name);
+                } else if kinds
+                    .iter()
+                    .any(|kind| kind.ends_with("lib") || kind == "proc-macro")
+                {
+                    lib_names.push(crate_name);
+                }
+            }
+            lib_names.extend(bin_names);
+            members.push((
+                order,
+                Package {
+                    name: package.to_member("name")?.required()?.try_into()?,
+                    crate_names: lib_names,
+                },
+            ));
+        }
+        members.sort_by_key(|(order, _)| *order);
+
+        Ok(Self {
+            root: value.to_member("workspace_root")?.required()?.try_into()?,
+            target_dir: value
+                .to_member("target_directory")?
+                .required()?
+                .try_into()?,
+            members: members.into_iter().map(|(_, package)| package).collect(),
+        })
+    }
+
+    /// Returns the directory where `cargo doc` writes JSON files
+    pub fn doc_dir(&self) -> std::path::PathBuf {
+        self.target_dir.join("doc")
+    }
+
+    /// Returns the crate name of a member package, given its package name
+    /// (or a name with underscores in place of dashes)
+    pub fn crate_name(&self, package_name: &str) -> Option<&str> {
+        let normalized = package_name.replace('-', "_");
+        self.members
+            .iter()
+            .find(|package| package.name.replace('-', "_") == normalized)
+            .and_then(|package| package.crate_names.first())
+            .map(|name| name.as_str())
+    }
+
+    /// Returns the position of the member package defining the crate (`None` for non-members)
+    pub fn member_index(&self, crate_name: &str) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|package| package.crate_names.iter().any(|name| name == crate_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_metadata() -> crate::Result<()> {
+        let text = r#"{
+            "packages": [
+                {"name": "my-app", "id": "path+file:///ws/app#my-app@0.1.0", "targets": [
+                    {"name": "my-app", "kind": ["bin"]}
+                ]},
+                {"name": "my-core", "id": "path+file:///ws/core#my-core@0.1.0", "targets": [
+                    {"name": "core_lib", "kind": ["lib"]},
+                    {"name": "bench-x", "kind": ["bench"]}
+                ]}
+            ],
+            "workspace_members": ["path+file:///ws/core#my-core@0.1.0", "path+file:///ws/app#my-app@0.1.0"],
+            "target_directory": "/tmp/target",
+            "workspace_root": "/ws"
+        }"#;
+        let workspace = Workspace::from_metadata(text)?;
+        assert_eq!(workspace.doc_dir(), std::path::Path::new("/tmp/target/doc"));
+        assert_eq!(workspace.crate_name("my-core"), Some("core_lib"));
+        assert_eq!(workspace.crate_name("my_app"), Some("my_app"));
+        assert_eq!(workspace.crate_name("serde"), None);
+        assert_eq!(workspace.member_index("core_lib"), Some(0));
+        assert_eq!(workspace.member_index("my_app"), Some(1));
+        assert_eq!(workspace.member_index("bench_x"), None);
+        Ok(())
+    }
+}